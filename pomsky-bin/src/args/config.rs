@@ -0,0 +1,183 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use glob::{MatchOptions, Pattern};
+use pomsky::{
+    features::PomskyFeatures,
+    options::{FlavorVersion, RegexFlavor},
+};
+use serde::Deserialize;
+
+use super::{DiagnosticSet, ParseArgsError};
+
+const FILE_NAME: &str = "pomsky.toml";
+
+/// Settings that can be set in `pomsky.toml`. Settings that aren't set fall
+/// back to the command-line flags, or the defaults.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Settings {
+    pub(crate) flavor: Option<RegexFlavor>,
+    pub(crate) flavor_version: Option<FlavorVersion>,
+    pub(crate) allowed_features: Option<PomskyFeatures>,
+    pub(crate) max_range_size: Option<u8>,
+    pub(crate) warnings: Option<DiagnosticSet>,
+}
+
+/// Settings for the files matching one of the glob patterns, which take
+/// precedence over the top-level settings in `pomsky.toml`
+#[derive(Debug, PartialEq)]
+pub(crate) struct Override {
+    /// The directory containing `pomsky.toml`, which the patterns are relative to
+    dir: PathBuf,
+    patterns: Vec<Pattern>,
+    pub(crate) settings: Settings,
+}
+
+impl Override {
+    pub(crate) fn matches(&self, path: &Path) -> bool {
+        let path = match std::env::current_dir() {
+            Ok(cwd) => cwd.join(path),
+            Err(_) => path.to_path_buf(),
+        };
+        let Ok(path) = path.strip_prefix(&self.dir) else { return false };
+
+        let options = MatchOptions { require_literal_separator: true, ..Default::default() };
+        self.patterns.iter().any(|pattern| pattern.matches_path_with(path, options))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct ConfigFile {
+    flavor: Option<String>,
+    allowed_features: Option<Vec<String>>,
+    max_range_size: Option<u8>,
+    warnings: Option<WarningsFile>,
+    #[serde(default, rename = "override")]
+    overrides: Vec<OverrideFile>,
+}
+
+/// An `[[override]]` section, which supports the same settings as the top level
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct OverrideFile {
+    files: OneOrMany,
+    flavor: Option<String>,
+    allowed_features: Option<Vec<String>>,
+    max_range_size: Option<u8>,
+    warnings: Option<WarningsFile>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Either `false` to disable all warnings, or a table such as
+/// `{ compat = false, P0105 = "deny" }`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WarningsFile {
+    All(bool),
+    Kinds(BTreeMap<String, WarningLevelFile>),
+}
+
+/// Either a boolean or a level, i.e. `"allow"`, `"warn"` or `"deny"`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WarningLevelFile {
+    Enabled(bool),
+    Level(String),
+}
+
+/// Looks for `pomsky.toml` in the current directory and its ancestors, and
+/// returns the top-level settings and the overrides it contains.
+pub(super) fn discover() -> Result<Option<(Settings, Vec<Override>)>, ParseArgsError> {
+    let Ok(cwd) = std::env::current_dir() else { return Ok(None) };
+
+    for dir in cwd.ancestors() {
+        let path = dir.join(FILE_NAME);
+        if path.is_file() {
+            return load(&path, dir).map(Some);
+        }
+    }
+    Ok(None)
+}
+
+fn load(path: &Path, dir: &Path) -> Result<(Settings, Vec<Override>), ParseArgsError> {
+    let error = |msg: String| ParseArgsError::Other(format!("{}: {msg}", path.display()));
+
+    let content = std::fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+    let file: ConfigFile = toml::from_str(&content).map_err(|e| error(e.to_string()))?;
+
+    let settings = resolve(file.flavor, file.allowed_features, file.max_range_size, file.warnings)
+        .map_err(|e| error(e.to_string()))?;
+    let overrides = file
+        .overrides
+        .into_iter()
+        .map(|o| {
+            let patterns = match o.files {
+                OneOrMany::One(pattern) => vec![pattern],
+                OneOrMany::Many(patterns) => patterns,
+            };
+            let patterns = patterns
+                .iter()
+                .map(|p| {
+                    Pattern::new(p).map_err(|e| {
+                        error(ParseArgsError::InvalidGlob(p.clone(), e.msg).to_string())
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+            let settings = resolve(o.flavor, o.allowed_features, o.max_range_size, o.warnings)
+                .map_err(|e| error(e.to_string()))?;
+            Ok(Override { dir: dir.to_path_buf(), patterns, settings })
+        })
+        .collect::<Result<_, ParseArgsError>>()?;
+
+    Ok((settings, overrides))
+}
+
+/// Parses the settings like the equivalent command-line flags
+fn resolve(
+    flavor: Option<String>,
+    allowed_features: Option<Vec<String>>,
+    max_range_size: Option<u8>,
+    warnings: Option<WarningsFile>,
+) -> Result<Settings, ParseArgsError> {
+    let (flavor, flavor_version) = match flavor {
+        Some(flavor) => {
+            let (flavor, version) = super::flavors::parse_flavor(flavor.into())?;
+            (Some(flavor), version)
+        }
+        None => (None, None),
+    };
+    let allowed_features = allowed_features
+        .map(|features| super::features::parse_features(features.join(",").into()))
+        .transpose()?;
+    let warnings = match warnings {
+        Some(WarningsFile::All(true)) | None => None,
+        Some(WarningsFile::All(false)) => {
+            Some(DiagnosticSet::parse("0".into(), Default::default())?)
+        }
+        Some(WarningsFile::Kinds(kinds)) => {
+            let rules = kinds
+                .into_iter()
+                .map(|(name, level)| match level {
+                    WarningLevelFile::Enabled(true) => format!("{name}=warn"),
+                    WarningLevelFile::Enabled(false) => format!("{name}=allow"),
+                    WarningLevelFile::Level(level) => format!("{name}={level}"),
+                })
+                .collect::<Vec<_>>();
+            match rules.is_empty() {
+                true => None,
+                false => Some(DiagnosticSet::parse(rules.join(",").into(), Default::default())?),
+            }
+        }
+    };
+
+    Ok(Settings { flavor, flavor_version, allowed_features, max_range_size, warnings })
+}
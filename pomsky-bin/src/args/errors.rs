@@ -7,9 +7,15 @@ pub(crate) enum ParseArgsError {
     UnexpectedTwice(&'static str),
     NoInput,
     InputAndPath,
+    FlavorAndCheckAllFlavors,
+    InvalidGlob(String, &'static str),
+    NoMatchingFiles(String),
+    WatchNoPaths,
+    OutDirNoPaths,
     UnknownFlavor(String),
+    UnknownLanguage(String),
     WarningsNoEquals(String),
-    WarningsNoZero(String),
+    WarningsInvalidLevel(String),
     WarningsNotAllowed(String),
     Other(String),
 }
@@ -33,22 +39,43 @@ impl fmt::Display for ParseArgsError {
             ParseArgsError::UnknownFlavor(flavor) => write!(
                 f,
                 "'{flavor}' isn't a valid flavor\n\
-                    possible values: pcre, python, java, javascript, dotnet, ruby, rust"
+                    possible values: pcre, python, python-regex, java, javascript, dotnet, ruby, rust, re2, ere, icu, vim, emacs"
+            ),
+            ParseArgsError::UnknownLanguage(language) => write!(
+                f,
+                "'{language}' isn't a valid language\n\
+                    possible values: rust, typescript, java"
             ),
             ParseArgsError::NoInput => write!(f, "No input provided"),
             ParseArgsError::InputAndPath => {
                 write!(f, "You can only provide an input or a path, but not both")
             }
+            ParseArgsError::FlavorAndCheckAllFlavors => {
+                write!(f, "You can only provide a flavor or '--check-all-flavors', but not both")
+            }
+            ParseArgsError::InvalidGlob(pattern, msg) => {
+                write!(f, "'{pattern}' isn't a valid glob pattern: {msg}")
+            }
+            ParseArgsError::NoMatchingFiles(pattern) => {
+                write!(f, "No files match the pattern '{pattern}'")
+            }
+            ParseArgsError::WatchNoPaths => {
+                write!(f, "'--watch' requires at least one file, directory or glob pattern")
+            }
+            ParseArgsError::OutDirNoPaths => {
+                write!(f, "'--out-dir' can only be used with '--path' or '--watch'")
+            }
             ParseArgsError::WarningsNoEquals(warning) => write!(
                 f,
                 "'{warning}' contains no '='\n\
                     try '-W{warning}=0' to disable {warning} warnings"
             ),
-            ParseArgsError::WarningsNoZero(warning) => {
-                write!(f, "warnings can only be disabled, try '-W{warning}=0'")
-            }
+            ParseArgsError::WarningsInvalidLevel(level) => write!(
+                f,
+                "'{level}' isn't a valid warning level, expected 'allow' (or '0'), 'warn' or 'deny'"
+            ),
             ParseArgsError::WarningsNotAllowed(warning) => {
-                write!(f, "`{warning}` diagnostic kind cannot be disabled")
+                write!(f, "`{warning}` diagnostics are errors and can't be configured")
             }
             ParseArgsError::Other(msg) => f.write_str(msg),
         }
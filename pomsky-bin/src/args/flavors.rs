@@ -1,19 +1,77 @@
 use std::ffi::OsString;
 
-use pomsky::options::RegexFlavor;
+use pomsky::{
+    diagnose::Feature,
+    options::{CompileOptions, EcmaVersion, FlavorVersion, RegexFlavor},
+};
 
 use super::ParseArgsError;
 
-pub(super) fn parse_flavor(value: OsString) -> Result<RegexFlavor, ParseArgsError> {
-    let lower = value.to_string_lossy().to_ascii_lowercase();
-    Ok(match lower.as_str() {
-        "pcre" => RegexFlavor::Pcre,
-        "python" => RegexFlavor::Python,
-        "java" => RegexFlavor::Java,
-        "js" | "javascript" => RegexFlavor::JavaScript,
-        "dotnet" | ".net" => RegexFlavor::DotNet,
-        "ruby" => RegexFlavor::Ruby,
-        "rust" => RegexFlavor::Rust,
-        _ => return Err(ParseArgsError::UnknownFlavor(lower)),
-    })
+/// Parses a flavor, which may be followed by `@` and the version of the regex
+/// engine, e.g. `js@es2018` or `python@3.11`
+pub(crate) fn parse_flavor(
+    value: OsString,
+) -> Result<(RegexFlavor, Option<FlavorVersion>), ParseArgsError> {
+    let value = value.to_string_lossy().to_ascii_lowercase();
+    let (name, version) = match value.split_once('@') {
+        Some((name, version)) => (name, Some(version)),
+        None => (value.as_str(), None),
+    };
+    let flavor =
+        name.parse::<RegexFlavor>().map_err(|_| ParseArgsError::UnknownFlavor(name.to_string()))?;
+    let Some(version) = version else {
+        return Ok((flavor, None));
+    };
+
+    match FlavorVersion::parse(flavor, version) {
+        Some(version) => Ok((flavor, Some(version))),
+        None => Err(ParseArgsError::Other(match flavor {
+            RegexFlavor::JavaScript | RegexFlavor::Python => format!(
+                "'{version}' isn't a valid version for '{name}', expected e.g. {}",
+                if flavor == RegexFlavor::Python { "'3.11'" } else { "'es2015' or 'es2018'" }
+            ),
+            _ => format!(
+                "a version can only be specified for 'javascript' and 'python', not for '{name}'"
+            ),
+        })),
+    }
+}
+
+/// Prints the features supported with the options used by the CLI, where
+/// emulations that must be enabled explicitly are unavailable
+pub(super) fn print_features(flavor: RegexFlavor, flavor_version: Option<FlavorVersion>) {
+    let options = CompileOptions { flavor, flavor_version, ..Default::default() };
+    let width = Feature::ALL.iter().map(|f| f.name().len()).max().unwrap_or_default() + 2;
+    for &feature in Feature::ALL {
+        println!("{:<width$}{}", feature.name(), options.supports(feature).name());
+    }
+}
+
+pub(crate) fn flavor_name(flavor: RegexFlavor) -> &'static str {
+    match flavor {
+        RegexFlavor::Pcre => "pcre",
+        RegexFlavor::Python => "python",
+        RegexFlavor::PythonRegex => "python-regex",
+        RegexFlavor::Java => "java",
+        RegexFlavor::JavaScript => "javascript",
+        RegexFlavor::DotNet => "dotnet",
+        RegexFlavor::Ruby => "ruby",
+        RegexFlavor::Rust => "rust",
+        RegexFlavor::RE2 => "re2",
+        RegexFlavor::PosixExtended => "ere",
+        RegexFlavor::Icu => "icu",
+        RegexFlavor::Vim => "vim",
+        RegexFlavor::Emacs => "emacs",
+        _ => "unknown",
+    }
+}
+
+/// Returns the version in the format accepted after `@` in `--flavor`
+pub(crate) fn version_name(version: FlavorVersion) -> String {
+    match version {
+        FlavorVersion::Python(major, minor) => format!("{major}.{minor}"),
+        FlavorVersion::JavaScript(EcmaVersion::Es2015) => "es2015".into(),
+        FlavorVersion::JavaScript(EcmaVersion::Es2018) => "es2018".into(),
+        _ => "unknown".into(),
+    }
 }
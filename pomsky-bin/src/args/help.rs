@@ -1,4 +1,7 @@
-use helptext::{sections, Help};
+use std::io::Write;
+
+use helptext::{sections, Color, Help, Segment};
+use pomsky::diagnose::DiagnosticCode;
 use supports_color::Stream;
 
 const USAGE: Help = Help(sections![
@@ -25,11 +28,46 @@ pub(super) const HELP: Help = Help(sections![
         command | pomsky [OPTIONS]"]
     }
 
+    "COMMANDS" {
+        table Auto {
+            "explain" => {
+                ["Explain a diagnostic code"]
+                Long ["Usage: " c:"pomsky explain <CODE>" "
+
+Prints a long-form description of an error or warning code, such as " c:"P0308" ",
+with an example and suggestions how to fix it."]
+            }
+            "gen" => {
+                ["Generate source files with a constant for each pomsky file"]
+                Long ["Usage: " c:"pomsky gen <PATH>... --lang <LANG>..." "
+
+Compiles the given files, directories or glob patterns, and generates a module
+for each language in " c:"--lang" " (" c:"rust" ", " c:"typescript" " or " c:"java" "). Each file
+becomes a constant named after the file, e.g. " c:"EMAIL_ADDRESS" "
+for " c:"email-address.pom" ". Its named capturing groups become constants
+containing the group index, e.g. " c:"EMAIL_ADDRESS_USER" ". The modules are
+written to " c:"--out-dir" ", which is required for multiple languages, or printed
+otherwise."]
+            }
+            "repl" => {
+                ["Start an interactive session"]
+                Long ["Each expression is compiled with the selected flavor, which can be changed
+with " c:":flavor <FLAVOR>" ". Variables declared on their own line,
+such as " g:"let x = 'test';" ", are kept across lines. Use " c:":test \"some text\"" " to
+match the last expression against a text with Rust's " c!"regex" " crate, and " c:":help" "
+to list all commands."]
+            }
+        }
+    }
+
     "ARGS" {
         table Auto {
             "<INPUT>" => {
                 ["Pomsky expression to compile"]
                 Long ["\n\
+                If several values are given, or the value is an existing file or directory,\n\
+                they are compiled as files, like with " c:"--path" ".\n\
+                \n\
                 To learn about the pomsky language, start here:\n\
                 https://pomsky-lang.org/docs/language-tour/basics/"]
             }
@@ -42,49 +80,130 @@ pub(super) const HELP: Help = Help(sections![
                 ["Comma-separated list of allowed features [default: all enabled]"]
                 Long ["Supported features are listed below."]
             }
+            "    --check-all-flavors" => {
+                ["Compile for every flavor and report which ones are supported"]
+                Long ["Prints a table with the result for each flavor: Whether it succeeded, how many
+warnings were emitted, or which regex feature isn't supported. The exit code is
+1 if any flavor failed. This can't be combined with " c:"--flavor" "."]
+            }
+            "    --deny-warnings" => {
+                ["Treat warnings as errors"]
+                Long ["Warnings can still be allowed or reported as warnings with " c:"-W" ",
+e.g. " c!"--deny-warnings -Wdeprecated=warn" ". The exit code is 4 if denied warnings
+are the only reason compilation failed."]
+            }
+            "    --emit <KIND>" => {
+                ["Print the " c:"regex" " or a string " c:"literal" " [default: " c:"regex" "]"]
+                Long ["With " c:"literal" ", the regex is printed as a literal that can be pasted into
+source code, with quotes and delimiters escaped. The language depends on the
+flavor, e.g. a Java string, a JavaScript regex literal with flags, a Python raw
+string, a C# verbatim string, a Ruby " c!"%r{...}" " literal, a PHP string for PCRE,
+or a Rust raw string."]
+            }
+            "    --explain <CODE>" => {
+                Long ["Explain a diagnostic code, the same as " c:"pomsky explain <CODE>"]
+            }
+            "    --fix <PATH>..." => {
+                ["Apply automatic fixes to files in place"]
+                Long ["Accepts files, directories and glob patterns like " c:"--path" ". Errors with an
+unambiguous fix, such as " c:"(?:" " or " c:"\\d" ", are fixed, and the files are compiled again,
+since fixing an error may reveal more. Remaining diagnostics are printed, and the
+exit code is 1 if any file still contains errors."]
+            }
             "-f, --flavor <FLAVOR>" => {
                 ["Regex flavor [default: " c:"pcre" "]"]
-                Long ["Supported flavors are listed below."]
+                Long ["Supported flavors are listed below. The version of " c:"javascript" " and " c:"python" "
+can be appended after " c:"@" ", e.g. " c:"js@es2018" " or " c:"python@3.11" "."]
             }
             "-h, --help" => {
                 ["Print help information"]
                 Long ["Use " c:"-h" " for short descriptions and " c:"--help" " for more details."]
             }
+            "    --lang <LANG>..." => {
+                Long ["Comma-separated list of languages for " c:"pomsky gen" "; can be " c:"rust" ", " c:"typescript" "
+or " c:"java"]
+            }
+            "    --list-features <FLAVOR>" => {
+                Long ["List the regex features and whether they are supported by a flavor, which
+can be " c:"yes" ", " c:"no" ", " c:"emulated" " or " c:"warning" ". A version can be appended to the
+flavor like in " c:"--flavor" ", e.g. " c:"js@es2018" "."]
+            }
+            "    --no-config" => {
+                ["Don't read settings from " c:"pomsky.toml"]
+                Long ["By default, pomsky looks for a " c:"pomsky.toml" " file in the current directory and
+its parents. It can set the " c:"flavor" ", " c:"allowed-features" ", " c:"max-range-size" "
+and " c:"warnings" ", and " c:"[[override]]" " sections can set them for files matching
+the glob patterns in " c:"files" ". Command-line flags take precedence."]
+            }
             "-n, --no-new-line" => {
                 ["Don't print a new-line after the output"]
             }
+            "-o, --out-dir <DIR>" => {
+                ["Write the compiled regexes to this directory"]
+                Long ["The regex compiled from each file is written to a file with the same name, but
+with the " c:".regex" " extension, e.g. " c:"email.regex" " for " c:"email.pom" ". Files with
+the same name in different directories are rejected.
+Requires " c:"--path" " or " c:"--watch" "."]
+            }
+            "    --output-format <FORMAT>" => {
+                ["Print the results as " c:"human" ", " c:"json" ", " c:"sarif" " or " c:"gnu" " [default: " c:"human" "]"]
+                Long [c:"json" " is the same as " c:"--json" ". With " c:"sarif" ", a SARIF 2.1.0 log with the
+diagnostics of all files is printed, which can be uploaded to code scanning
+tools; the compiled regexes aren't printed. With " c:"gnu" ", diagnostics are
+printed as " c!"file:line:column: severity: message" ", which is understood by
+editors and " c!"make" ". Lines and columns start at 1, and columns are counted in
+code points."]
+            }
             "-p, --path <FILE>" => {
                 ["File containing the pomsky expression to compile"]
+                Long ["Can be given multiple times. Glob patterns such as " c:"'patterns/*.pom'" " are
+expanded. When more than one file is given, each file is compiled separately and
+its regex is printed after its path. With " c:"--json" ", a single object is printed
+that maps each path to its result. The exit code is 1 if any file failed to
+compile."]
             }
             "-V, --version" => {
                 ["Print version information"]
             }
             "-W, --warnings <DIAGNOSTICS>" => {
                 Short ["Disable certain warnings (disable all with " c:"-W0" ")"]
-                Long ["Disable some or all warnings. A single warning can be disabled by specifying
-the name followed by " c:"=0" ", for example:
+                Long ["Disable, enable or deny some or all warnings. A warning can be configured by
+specifying its kind or code followed by " c:"=allow" " (or " c:"=0" "), " c:"=warn" " or " c:"=deny" ", for
+example:
 
-    " c!"-Wcompat=0" "
+    " c!"-Wcompat=0
+    -WP0400=deny" "
 
-Multiple warnings can be disabled by setting this option multiple times, or
+Multiple warnings can be configured by setting this option multiple times, or
 using a comma-separated list:
 
     " c!"-Wcompat=0 -Wdeprecated=0
     -Wcompat=0,deprecated=0" "
 
-To disable all warnings, use " c:"-W0" ".
+Codes take precedence over kinds. To disable all warnings, use " c:"-W0" ". Denied
+warnings are reported as errors, and the exit code is 4 if they are the only
+reason compilation failed.
 
-Currently, the following warnings can be disabled:"]
+Currently, the following warnings can be configured:"]
                 Long table Compact {
                     "compat"     => { ["Compatibility warnings"] }
                     "deprecated" => { ["A used feature will be removed in the future"] }
+                    "P0105"      => { ["Deprecated syntax"] }
+                    "P0400"      => { ["A feature may not be supported in all regex engines"] }
                 }
             }
+            "-w, --watch <PATH>..." => {
+                ["Recompile files whenever they change"]
+                Long ["Accepts files, directories and glob patterns. Directories are searched
+recursively for " c:".pom" " files. The files are checked for changes twice per
+second, and files that are added later are picked up as well. Diagnostics are
+printed, and the compiled regexes are printed or written to " c:"--out-dir" "."]
+            }
             "-d, --debug" => {
                 Long ["Show debug information"]
             }
             "    --json" => {
-                Long ["Return output as JSON"]
+                Long ["Return output as JSON, the same as " c:"--output-format=json"]
             }
         }
     }
@@ -93,11 +212,17 @@ Currently, the following warnings can be disabled:"]
         table Compact {
             "pcre"       => { ["PCRE/PCRE2 regex engines, compatible with Perl, PHP and R"] }
             "python"     => { ["Python's " c!"re" " module"] }
+            "python-regex" => { ["Python's third-party " c!"regex" " module"] }
             "java"       => { ["Java's " c!"Pattern" " class, compatible with Kotlin and Scala"] }
             "javascript" => { ["ECMAScript regular expressions"] }
             "dotnet"     => { [c!"Regex" " class in .NET languages such C# and F#"] }
             "ruby"       => { ["Ruby's built-in regular expressions"] }
             "rust"       => { ["Rust's " c!"regex" " crate"] }
+            "re2"        => { ["RE2 and Go's " c!"regexp" " package"] }
+            "ere"        => { ["POSIX extended regular expressions, used by " c!"grep -E" ", " c!"awk" " and " c!"sed -E"] }
+            "icu"        => { ["ICU regular expressions, used by Swift's " c!"NSRegularExpression" " and MySQL 8"] }
+            "vim"        => { ["Vim's regular expressions in very magic mode (" c!"\\v" ")"] }
+            "emacs"      => { ["Emacs Lisp regular expressions"] }
         }
     }
 
@@ -142,6 +267,34 @@ fn is_colored(stream: Stream) -> bool {
     )
 }
 
+/// Prints the explanation of a diagnostic code. Code examples are printed in
+/// green and code within a sentence in cyan.
+pub(crate) fn print_explanation(code: DiagnosticCode) {
+    let colored = is_colored(Stream::Stdout);
+    let mut buf = std::io::stdout().lock();
+
+    let explanation = code.explanation();
+    let code = code.to_string();
+    let _ =
+        Segment { style: Some(Color::Y), text: &code, ticks: false }.write(&mut buf, colored, 0);
+    let _ = buf.write_all(b"\n\n");
+
+    for line in explanation.lines() {
+        if let Some(example) = line.strip_prefix("    ") {
+            let _ = buf.write_all(b"    ");
+            let segment = Segment { style: Some(Color::g), text: example, ticks: false };
+            let _ = segment.write(&mut buf, colored, 0);
+        } else {
+            // code within a sentence is enclosed in backticks
+            for (i, text) in line.split('`').enumerate() {
+                let style = if i % 2 == 1 { Some(Color::c) } else { None };
+                let _ = Segment { style, text, ticks: true }.write(&mut buf, colored, 0);
+            }
+        }
+        let _ = buf.write_all(b"\n");
+    }
+}
+
 pub(crate) fn print_version() {
     println!(concat!("pomsky ", env!("CARGO_PKG_VERSION")));
 }
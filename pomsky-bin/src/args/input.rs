@@ -1,4 +1,8 @@
-use std::{io::Read, path::PathBuf};
+use std::{
+    ffi::OsString,
+    io::Read,
+    path::{Path, PathBuf},
+};
 
 use atty::Stream;
 
@@ -9,6 +13,21 @@ use super::ParseArgsError;
 pub(crate) enum Input {
     Value(String),
     File(PathBuf),
+    /// Multiple files, or files matched by a glob pattern, which are compiled
+    /// separately
+    Files(Vec<PathBuf>),
+    /// Files, glob patterns or directories that are watched for changes
+    Watch(Vec<OsString>),
+    /// Files whose quick fixes are applied in place
+    Fix(Vec<PathBuf>),
+    /// The `repl` command, which reads expressions interactively
+    Repl,
+    /// The `gen` command, which generates a source file for each language
+    /// from the given files
+    Generate {
+        files: Vec<PathBuf>,
+        languages: Vec<super::Language>,
+    },
 }
 
 impl Input {
@@ -25,4 +44,60 @@ impl Input {
             Err(ParseArgsError::NoInput)
         }
     }
+
+    /// Returns [`Input::File`] if a single file is given, and [`Input::Files`]
+    /// with the matched files if several paths, a directory or a glob pattern
+    /// are given.
+    pub(crate) fn from_paths(paths: Vec<OsString>) -> Result<Self, ParseArgsError> {
+        let mut files = Self::expand_paths(&paths, false)?;
+
+        match &*paths {
+            [path] if !is_pattern(path) => Ok(Input::File(files.pop().unwrap())),
+            _ => Ok(Input::Files(files)),
+        }
+    }
+
+    /// Returns the files matched by the given paths. Glob patterns are
+    /// expanded, and directories are searched recursively for `.pom` files.
+    /// Unless `allow_empty` is true, it is an error if a pattern or directory
+    /// matches no files.
+    pub(crate) fn expand_paths(
+        paths: &[OsString],
+        allow_empty: bool,
+    ) -> Result<Vec<PathBuf>, ParseArgsError> {
+        let mut files = Vec::new();
+
+        for path in paths {
+            if !is_pattern(path) {
+                files.push(PathBuf::from(path));
+                continue;
+            }
+
+            let pattern = if Path::new(path).is_dir() {
+                let dir = glob::Pattern::escape(&path.to_string_lossy());
+                format!("{}/**/*.pom", dir.trim_end_matches('/'))
+            } else {
+                path.to_string_lossy().into_owned()
+            };
+            let matches = glob::glob(&pattern)
+                .map_err(|e| ParseArgsError::InvalidGlob(pattern.clone(), e.msg))?;
+            let len = files.len();
+            files.extend(matches.filter_map(Result::ok).filter(|path| path.is_file()));
+            if files.len() == len && !allow_empty {
+                return Err(ParseArgsError::NoMatchingFiles(pattern));
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+/// Whether the path is a glob pattern or a directory, which may match any
+/// number of files. A file that exists is not a pattern, even if its name
+/// contains `*`, `?` or `[`.
+fn is_pattern(path: &OsString) -> bool {
+    match Path::new(path) {
+        path if path.exists() => path.is_dir(),
+        _ => path.to_string_lossy().contains(['*', '?', '[']),
+    }
 }
@@ -0,0 +1,60 @@
+use std::ffi::OsString;
+
+use pomsky::options::RegexFlavor;
+
+use super::ParseArgsError;
+
+/// A programming language for which `pomsky gen` generates source code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Language {
+    Rust,
+    TypeScript,
+    Java,
+}
+
+impl Language {
+    /// The regex flavor used by the language
+    pub(crate) fn flavor(self) -> RegexFlavor {
+        match self {
+            Language::Rust => RegexFlavor::Rust,
+            Language::TypeScript => RegexFlavor::JavaScript,
+            Language::Java => RegexFlavor::Java,
+        }
+    }
+
+    /// The name of the generated file
+    pub(crate) fn file_name(self) -> &'static str {
+        match self {
+            Language::Rust => "patterns.rs",
+            Language::TypeScript => "patterns.ts",
+            Language::Java => "Patterns.java",
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Language::Rust => "rust",
+            Language::TypeScript => "typescript",
+            Language::Java => "java",
+        }
+    }
+}
+
+pub(super) fn parse_languages(value: OsString) -> Result<Vec<Language>, ParseArgsError> {
+    let lower = value.to_string_lossy().to_ascii_lowercase();
+
+    let mut languages = Vec::new();
+    for part in lower.split(',') {
+        let language = match part.trim() {
+            "" => continue,
+            "rust" | "rs" => Language::Rust,
+            "typescript" | "ts" => Language::TypeScript,
+            "java" => Language::Java,
+            s => return Err(ParseArgsError::UnknownLanguage(s.to_string())),
+        };
+        if !languages.contains(&language) {
+            languages.push(language);
+        }
+    }
+    Ok(languages)
+}
@@ -1,20 +1,64 @@
-use pomsky::{features::PomskyFeatures, options::RegexFlavor};
+use std::path::{Path, PathBuf};
 
+use pomsky::{
+    features::PomskyFeatures,
+    options::{FlavorVersion, RegexFlavor},
+};
+
+pub(crate) use config::{Override, Settings};
 pub(crate) use errors::ParseArgsError;
+pub(crate) use flavors::{flavor_name, parse_flavor, version_name};
 pub(crate) use help::print_short_usage_and_help_err;
 pub(crate) use input::Input;
-pub(crate) use warnings::DiagnosticSet;
+pub(crate) use languages::Language;
+pub(crate) use warnings::{DiagnosticSet, WarningLevel};
 
 use self::parse::ArgsInner;
 
+mod config;
 mod errors;
 mod features;
 mod flavors;
 mod help;
 mod input;
+mod languages;
 mod parse;
 mod warnings;
 
+/// What is printed for a compiled expression
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Emit {
+    /// The regex
+    #[default]
+    Regex,
+    /// A string or regex literal of the language that is typically used with
+    /// the flavor
+    Literal,
+}
+
+/// How the results and diagnostics are printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum OutputFormat {
+    /// Diagnostics with code snippets, for humans
+    #[default]
+    Human,
+    /// A `CompilationResult` object, or a map of them
+    Json,
+    /// A SARIF 2.1.0 log, for code scanning tools
+    Sarif,
+    /// Diagnostics in the format `file:line:column: severity: message`, for
+    /// editors and `make`
+    Gnu,
+}
+
+impl OutputFormat {
+    /// Whether the result is printed as a single document at the end, instead
+    /// of printing diagnostics as they're emitted
+    pub(crate) fn is_structured(self) -> bool {
+        matches!(self, OutputFormat::Json | OutputFormat::Sarif)
+    }
+}
+
 /// Compile a Pomsky expression to a regex
 #[derive(PartialEq)]
 pub(crate) struct Args {
@@ -22,16 +66,75 @@ pub(crate) struct Args {
     pub(crate) input: Input,
     /// Show debug information
     pub(crate) debug: bool,
-    /// Whether output should be provided as JSON
-    pub(crate) json: bool,
+    /// How the results and diagnostics are printed
+    pub(crate) output_format: OutputFormat,
     /// Regex flavor
     pub(crate) flavor: Option<RegexFlavor>,
+    /// Version of the regex flavor, e.g. `es2018` in `--flavor js@es2018`
+    pub(crate) flavor_version: Option<FlavorVersion>,
+    /// Compile for every regex flavor and report the results
+    pub(crate) check_all_flavors: bool,
+    /// Directory where the compiled regexes are written to, one file per input
+    /// file
+    pub(crate) out_dir: Option<PathBuf>,
+    /// Whether the regex or a string literal is printed
+    pub(crate) emit: Emit,
     /// Does not print a new-line at the end of the compiled regular expression
     pub(crate) no_new_line: bool,
     /// Set of allowed pomsky features
     pub(crate) allowed_features: PomskyFeatures,
     /// Set of warnings that should be emitted
     pub(crate) warnings: DiagnosticSet,
+    /// Maximum number of digits in a `range` expression
+    pub(crate) max_range_size: u8,
+    /// Per-file settings from `pomsky.toml`, without the settings that were
+    /// provided on the command line
+    pub(crate) overrides: Vec<Override>,
+}
+
+impl Args {
+    /// Returns the settings of the overrides matching the file, in the order
+    /// in which they appear in `pomsky.toml`
+    fn overrides_for<'a>(&'a self, path: Option<&'a Path>) -> impl Iterator<Item = &'a Settings> {
+        self.overrides
+            .iter()
+            .filter(move |o| path.map(|path| o.matches(path)).unwrap_or(false))
+            .map(|o| &o.settings)
+    }
+
+    pub(crate) fn flavor_for(&self, path: Option<&Path>) -> Option<RegexFlavor> {
+        self.overrides_for(path).filter_map(|s| s.flavor).last().or(self.flavor)
+    }
+
+    /// Returns the version belonging to the flavor returned by
+    /// [`Args::flavor_for`]
+    pub(crate) fn flavor_version_for(&self, path: Option<&Path>) -> Option<FlavorVersion> {
+        match self.overrides_for(path).filter(|s| s.flavor.is_some()).last() {
+            Some(settings) => settings.flavor_version,
+            None => self.flavor_version,
+        }
+    }
+
+    pub(crate) fn allowed_features_for(&self, path: Option<&Path>) -> PomskyFeatures {
+        self.overrides_for(path)
+            .filter_map(|s| s.allowed_features)
+            .last()
+            .unwrap_or(self.allowed_features)
+    }
+
+    pub(crate) fn max_range_size_for(&self, path: Option<&Path>) -> u8 {
+        self.overrides_for(path)
+            .filter_map(|s| s.max_range_size)
+            .last()
+            .unwrap_or(self.max_range_size)
+    }
+
+    pub(crate) fn warnings_for<'a>(&'a self, path: Option<&'a Path>) -> &'a DiagnosticSet {
+        self.overrides_for(path)
+            .filter_map(|s| s.warnings.as_ref())
+            .last()
+            .unwrap_or(&self.warnings)
+    }
 }
 
 pub(super) fn parse_args() -> Result<Args, ParseArgsError> {
@@ -49,5 +152,13 @@ pub(super) fn parse_args() -> Result<Args, ParseArgsError> {
             help::print_version();
             std::process::exit(0)
         }
+        ArgsInner::Explain(code) => {
+            help::print_explanation(code);
+            std::process::exit(0)
+        }
+        ArgsInner::ListFeatures(flavor, version) => {
+            flavors::print_features(flavor, version);
+            std::process::exit(0)
+        }
     }
 }
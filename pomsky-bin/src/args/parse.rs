@@ -1,6 +1,12 @@
+use std::{ffi::OsString, path::Path};
+
 use atty::Stream;
+use pomsky::{
+    diagnose::DiagnosticCode,
+    options::{FlavorVersion, RegexFlavor},
+};
 
-use super::{Args, DiagnosticSet, Input, ParseArgsError};
+use super::{config, Args, DiagnosticSet, Emit, Input, Language, OutputFormat, ParseArgsError};
 
 #[derive(PartialEq)]
 pub(super) enum ArgsInner {
@@ -8,43 +14,98 @@ pub(super) enum ArgsInner {
     HelpShort,
     HelpLong,
     Version,
+    ListFeatures(RegexFlavor, Option<FlavorVersion>),
+    Explain(DiagnosticCode),
 }
 
 pub(super) fn parse_args_inner(mut parser: lexopt::Parser) -> Result<ArgsInner, ParseArgsError> {
     use lexopt::prelude::*;
 
     let mut arg_count = 0;
-    let mut input_value = None;
-    let mut path = None;
+    let mut values = Vec::new();
+    let mut paths = Vec::new();
     let mut debug = false;
     let mut flavor = None;
+    let mut check_all_flavors = false;
     let mut no_new_line = false;
     let mut allowed_features = None;
-    let mut warnings = DiagnosticSet::All;
+    let mut warnings = None;
     let mut json = false;
+    let mut output_format = None;
+    let mut watch = false;
+    let mut fix = false;
+    let mut out_dir = None;
+    let mut repl = false;
+    let mut emit = None;
+    let mut generate = false;
+    let mut languages = None;
+    let mut no_config = false;
+    let mut deny_warnings = false;
 
     while let Some(arg) = parser.next()? {
         arg_count += 1;
 
         match arg {
-            Short('p') | Long("path") => path.set_arg(parser.value()?.parse()?, "--path")?,
+            Short('p') | Long("path") => paths.push(parser.value()?),
+            Short('w') | Long("watch") => {
+                watch.set_arg(true, "--watch")?;
+                // the paths may also be provided with `--path`
+                match parser.values() {
+                    Ok(values) => paths.extend(values),
+                    Err(lexopt::Error::MissingValue { .. }) => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            Long("fix") => {
+                fix.set_arg(true, "--fix")?;
+                // the paths may also be provided with `--path`
+                match parser.values() {
+                    Ok(values) => paths.extend(values),
+                    Err(lexopt::Error::MissingValue { .. }) => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            Short('o') | Long("out-dir") => out_dir.set_arg(parser.value()?.into(), "--out-dir")?,
             Short('d') | Long("debug") => debug.set_arg(true, "--debug")?,
             Short('f') | Long("flavor") => {
                 flavor.set_arg(super::flavors::parse_flavor(parser.value()?)?, "--flavor")?;
             }
+            Long("check-all-flavors") => {
+                check_all_flavors.set_arg(true, "--check-all-flavors")?;
+            }
             Short('n') | Long("no-new-line") => no_new_line.set_arg(true, "--no-new-line")?,
             Short('W') | Long("warnings") => {
-                warnings = DiagnosticSet::parse(parser.value()?, warnings)?;
+                let prev = warnings.unwrap_or_default();
+                warnings = Some(DiagnosticSet::parse(parser.value()?, prev)?);
             }
+            Long("deny-warnings") => deny_warnings.set_arg(true, "--deny-warnings")?,
             Long("allowed-features") => allowed_features
                 .set_arg(super::features::parse_features(parser.value()?)?, "--allowed-features")?,
             Long("json") => json.set_arg(true, "--json")?,
-            Value(val) if input_value.is_none() => {
-                input_value = Some(val.into_string().map_err(lexopt::Error::from)?);
+            Long("output-format") => {
+                output_format.set_arg(parse_output_format(parser.value()?)?, "--output-format")?
+            }
+            Long("no-config") => no_config.set_arg(true, "--no-config")?,
+            Long("emit") => emit.set_arg(parse_emit(parser.value()?)?, "--emit")?,
+            Value(val) if arg_count == 1 && val == "explain" => {
+                return Ok(ArgsInner::Explain(parse_code(parser.value()?)?));
             }
+            Long("explain") => return Ok(ArgsInner::Explain(parse_code(parser.value()?)?)),
+            Value(val) if arg_count == 1 && val == "repl" => repl = true,
+            Value(val) if arg_count == 1 && val == "gen" => generate = true,
+            Value(val) if generate => paths.push(val),
+            Long("lang") => {
+                languages.set_arg(super::languages::parse_languages(parser.value()?)?, "--lang")?
+            }
+            // several values are paths, but not in addition to `--path`
+            Value(val) if values.is_empty() || paths.is_empty() => values.push(val),
             Short('h') => return Ok(ArgsInner::HelpShort),
             Long("help") => return Ok(ArgsInner::HelpLong),
             Short('V') | Long("version") => return Ok(ArgsInner::Version),
+            Long("list-features") => {
+                let (flavor, version) = super::flavors::parse_flavor(parser.value()?)?;
+                return Ok(ArgsInner::ListFeatures(flavor, version));
+            }
             _ => Err(arg.unexpected())?,
         }
     }
@@ -53,24 +114,211 @@ pub(super) fn parse_args_inner(mut parser: lexopt::Parser) -> Result<ArgsInner,
         return Ok(ArgsInner::HelpShort);
     }
 
-    let input = match (input_value, path) {
-        (Some(input), None) => Input::Value(input),
-        (None, Some(path)) => Input::File(path),
-        (Some(_), Some(_)) => return Err(ParseArgsError::InputAndPath),
-        (None, None) => Input::read_stdin()?,
+    // e.g. `pomsky patterns/*.pom`, where the shell expands the glob pattern
+    let mut input_value = None;
+    if values.len() > 1 || (paths.is_empty() && values.iter().any(|v| Path::new(v).exists())) {
+        paths.append(&mut values);
+    } else if let Some(value) = values.pop() {
+        input_value = Some(value.into_string().map_err(lexopt::Error::from)?);
+    }
+
+    if check_all_flavors && flavor.is_some() {
+        return Err(ParseArgsError::FlavorAndCheckAllFlavors);
+    }
+
+    if repl && (input_value.is_some() || !paths.is_empty()) {
+        return Err(ParseArgsError::Other(
+            "'repl' can't be used with an input, '--path' or '--watch'".into(),
+        ));
+    }
+
+    if watch && paths.is_empty() {
+        return Err(ParseArgsError::WatchNoPaths);
+    }
+
+    if fix {
+        if repl || generate || watch {
+            return Err(ParseArgsError::Other(
+                "'--fix' can't be used with 'repl', 'gen' or '--watch'".into(),
+            ));
+        }
+        if input_value.is_some() || paths.is_empty() {
+            return Err(ParseArgsError::Other(
+                "'--fix' requires at least one file, directory or glob pattern".into(),
+            ));
+        }
+    }
+
+    let mut input = match (input_value, paths.is_empty()) {
+        _ if repl => Input::Repl,
+        _ if generate => parse_generate(paths, languages, flavor.is_some(), out_dir.is_some())?,
+        _ if fix => Input::Fix(Input::expand_paths(&paths, false)?),
+        (Some(input), true) => Input::Value(input),
+        (None, false) if watch => {
+            // report invalid patterns before starting to watch
+            Input::expand_paths(&paths, true)?;
+            Input::Watch(paths)
+        }
+        (None, false) => Input::from_paths(paths)?,
+        (Some(_), false) => return Err(ParseArgsError::InputAndPath),
+        (None, true) => Input::read_stdin()?,
     };
 
+    if check_all_flavors
+        && matches!(
+            input,
+            Input::Files(_) | Input::Watch(_) | Input::Fix(_) | Input::Generate { .. }
+        )
+    {
+        return Err(ParseArgsError::Other(
+            "'--check-all-flavors' can only be used with a single expression or file".into(),
+        ));
+    }
+
+    // `--json` is short for `--output-format=json`
+    let output_format = match (json, output_format) {
+        (false, output_format) => output_format.unwrap_or_default(),
+        (true, None) => OutputFormat::Json,
+        (true, Some(_)) => {
+            return Err(ParseArgsError::Other(
+                "'--json' can't be used with '--output-format'".into(),
+            ));
+        }
+    };
+    if fix && output_format.is_structured() {
+        return Err(ParseArgsError::Other(
+            "'--fix' can only be used with '--output-format=human' or 'gnu'".into(),
+        ));
+    }
+    if output_format == OutputFormat::Sarif {
+        if check_all_flavors {
+            return Err(ParseArgsError::Other(
+                "'--output-format=sarif' can't be used with '--check-all-flavors'".into(),
+            ));
+        }
+        if matches!(input, Input::Watch(_) | Input::Repl | Input::Generate { .. }) {
+            return Err(ParseArgsError::Other(
+                "'--output-format=sarif' can't be used with 'repl', 'gen' or '--watch'".into(),
+            ));
+        }
+    }
+
+    if out_dir.is_some() {
+        input = match input {
+            Input::Value(_) | Input::Repl => return Err(ParseArgsError::OutDirNoPaths),
+            Input::Fix(_) => {
+                return Err(ParseArgsError::Other("'--out-dir' can't be used with '--fix'".into()))
+            }
+            Input::File(path) => Input::Files(vec![path]),
+            input => input,
+        };
+    }
+
+    // settings on the command line take precedence over `pomsky.toml`
+    let (settings, mut overrides) = match no_config {
+        true => Default::default(),
+        false => config::discover()?.unwrap_or_default(),
+    };
+    for o in &mut overrides {
+        if flavor.is_some() {
+            o.settings.flavor = None;
+            o.settings.flavor_version = None;
+        }
+        if allowed_features.is_some() {
+            o.settings.allowed_features = None;
+        }
+        if warnings.is_some() {
+            o.settings.warnings = None;
+        }
+        if let (Some(warnings), true) = (&mut o.settings.warnings, deny_warnings) {
+            warnings.deny_all();
+        }
+    }
+    let mut warnings = warnings.or(settings.warnings).unwrap_or_default();
+    if deny_warnings {
+        warnings.deny_all();
+    }
+    let (flavor, flavor_version) = match flavor {
+        Some((flavor, version)) => (Some(flavor), version),
+        None => (settings.flavor, settings.flavor_version),
+    };
     Ok(ArgsInner::Args(Args {
         input,
         flavor,
+        flavor_version,
+        check_all_flavors,
         debug,
-        json,
+        output_format,
+        out_dir,
+        emit: emit.unwrap_or_default(),
         no_new_line,
-        allowed_features: allowed_features.unwrap_or_default(),
+        allowed_features: allowed_features.or(settings.allowed_features).unwrap_or_default(),
         warnings,
+        max_range_size: settings.max_range_size.unwrap_or(12),
+        overrides,
     }))
 }
 
+fn parse_generate(
+    paths: Vec<OsString>,
+    languages: Option<Vec<Language>>,
+    has_flavor: bool,
+    has_out_dir: bool,
+) -> Result<Input, ParseArgsError> {
+    let languages = match languages {
+        Some(languages) if !languages.is_empty() => languages,
+        _ => return Err(ParseArgsError::Other("'gen' requires '--lang'".into())),
+    };
+    if has_flavor {
+        return Err(ParseArgsError::Other(
+            "'gen' can't be used with '--flavor', the flavor is determined by the language".into(),
+        ));
+    }
+    if languages.len() > 1 && !has_out_dir {
+        return Err(ParseArgsError::Other(
+            "'gen' requires '--out-dir' when generating code for multiple languages".into(),
+        ));
+    }
+    if paths.is_empty() {
+        return Err(ParseArgsError::Other(
+            "'gen' requires at least one file, directory or glob pattern".into(),
+        ));
+    }
+
+    let files = Input::expand_paths(&paths, false)?;
+    Ok(Input::Generate { files, languages })
+}
+
+fn parse_code(value: OsString) -> Result<DiagnosticCode, ParseArgsError> {
+    let value = value.to_string_lossy();
+    value.parse().map_err(|_| {
+        ParseArgsError::Other(format!("'{value}' isn't a valid diagnostic code, e.g. 'P0308'"))
+    })
+}
+
+fn parse_output_format(value: OsString) -> Result<OutputFormat, ParseArgsError> {
+    match value.to_string_lossy().as_ref() {
+        "human" => Ok(OutputFormat::Human),
+        "json" => Ok(OutputFormat::Json),
+        "sarif" => Ok(OutputFormat::Sarif),
+        "gnu" => Ok(OutputFormat::Gnu),
+        value => Err(ParseArgsError::Other(format!(
+            "'{value}' isn't a valid value for '--output-format', expected 'human', 'json', \
+            'sarif' or 'gnu'"
+        ))),
+    }
+}
+
+fn parse_emit(value: OsString) -> Result<Emit, ParseArgsError> {
+    match value.to_string_lossy().as_ref() {
+        "regex" => Ok(Emit::Regex),
+        "literal" => Ok(Emit::Literal),
+        value => Err(ParseArgsError::Other(format!(
+            "'{value}' isn't a valid value for '--emit', expected 'regex' or 'literal'"
+        ))),
+    }
+}
+
 trait SetArg {
     type Set;
 
@@ -1,65 +1,114 @@
 use std::{ffi::OsString, str::FromStr};
 
-use pomsky::diagnose::DiagnosticKind;
+use pomsky::diagnose::{Diagnostic, DiagnosticCode, DiagnosticKind};
 
 use super::ParseArgsError;
 
+/// How a warning is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WarningLevel {
+    /// The warning isn't shown
+    Allow,
+    /// The warning is shown
+    Warn,
+    /// The warning is shown as an error, and the expression fails to compile
+    Deny,
+}
+
+impl WarningLevel {
+    fn parse(value: &str) -> Option<Self> {
+        Some(match value {
+            "0" | "allow" => WarningLevel::Allow,
+            "1" | "warn" => WarningLevel::Warn,
+            "deny" => WarningLevel::Deny,
+            _ => return None,
+        })
+    }
+}
+
+/// The level of each warning. Warnings can be configured by their kind, e.g.
+/// `compat`, or by their code, e.g. `P0400`. Codes take precedence over kinds.
 #[derive(Debug, PartialEq)]
-pub(crate) enum DiagnosticSet {
-    All,
-    Disabled(Vec<DiagnosticKind>),
-    Enabled(Vec<DiagnosticKind>),
+pub(crate) struct DiagnosticSet {
+    /// Level of warnings that aren't configured individually
+    default: WarningLevel,
+    kinds: Vec<(DiagnosticKind, WarningLevel)>,
+    codes: Vec<(DiagnosticCode, WarningLevel)>,
+}
+
+impl Default for DiagnosticSet {
+    fn default() -> Self {
+        DiagnosticSet { default: WarningLevel::Warn, kinds: vec![], codes: vec![] }
+    }
 }
 
 impl DiagnosticSet {
-    pub(crate) fn is_enabled(&self, kind: DiagnosticKind) -> bool {
-        match self {
-            DiagnosticSet::All => true,
-            DiagnosticSet::Disabled(set) => !set.contains(&kind),
-            DiagnosticSet::Enabled(set) => set.contains(&kind),
-        }
+    pub(crate) fn level(&self, diagnostic: &Diagnostic) -> WarningLevel {
+        let code = diagnostic.code.and_then(|code| {
+            self.codes.iter().rev().find(|&&(c, _)| c == code).map(|&(_, level)| level)
+        });
+        let kind = || {
+            self.kinds.iter().rev().find(|&&(k, _)| k == diagnostic.kind).map(|&(_, level)| level)
+        };
+        code.or_else(kind).unwrap_or(self.default)
+    }
+
+    pub(crate) fn is_enabled(&self, diagnostic: &Diagnostic) -> bool {
+        self.level(diagnostic) != WarningLevel::Allow
+    }
+
+    /// Denies all warnings that aren't configured individually
+    pub(super) fn deny_all(&mut self) {
+        self.default = WarningLevel::Deny;
     }
 
+    /// Parses a comma-separated list such as `compat=0,P0105=deny`. `0` alone
+    /// disables all warnings.
     pub(super) fn parse(value: OsString, mut warnings: Self) -> Result<Self, ParseArgsError> {
         let value = value.to_string_lossy();
         if value.as_ref() == "0" {
-            Ok(DiagnosticSet::Enabled(vec![]))
-        } else {
-            let mut warning_list_own = vec![];
-            let warning_list = match &mut warnings {
-                DiagnosticSet::Disabled(set) => set,
-                DiagnosticSet::Enabled(_) => return Ok(warnings),
-                DiagnosticSet::All => &mut warning_list_own,
-            };
-
-            for warning in value.split(',') {
-                let (kind_str, val) = warning
-                    .trim_start()
-                    .rsplit_once('=')
-                    .ok_or_else(|| ParseArgsError::WarningsNoEquals(warning.to_string()))?;
-
-                if val != "0" {
-                    return Err(ParseArgsError::WarningsNoZero(kind_str.to_string()));
-                }
-
-                let kind = DiagnosticKind::from_str(kind_str).map_err(|_| {
-                    ParseArgsError::Other(format!(
-                        "`{kind_str}` is not a recognized diagnostic kind"
-                    ))
-                })?;
+            return Ok(DiagnosticSet {
+                default: WarningLevel::Allow,
+                kinds: vec![],
+                codes: vec![],
+            });
+        }
 
-                let (DiagnosticKind::Compat | DiagnosticKind::Deprecated) = kind else {
-                return Err(ParseArgsError::WarningsNotAllowed(kind_str.to_string()))
-            };
+        for warning in value.split(',') {
+            let (name, level) = warning
+                .trim_start()
+                .rsplit_once('=')
+                .ok_or_else(|| ParseArgsError::WarningsNoEquals(warning.to_string()))?;
 
-                warning_list.push(kind);
-            }
+            let level = WarningLevel::parse(level)
+                .ok_or_else(|| ParseArgsError::WarningsInvalidLevel(level.to_string()))?;
+
+            if name.starts_with('P') {
+                let Ok(code) = name.parse::<DiagnosticCode>() else {
+                    return Err(ParseArgsError::Other(format!(
+                        "`{name}` is not a recognized diagnostic code"
+                    )));
+                };
 
-            if matches!(warnings, DiagnosticSet::All) {
-                Ok(DiagnosticSet::Disabled(warning_list_own))
+                let (DiagnosticCode::DeprecatedSyntax | DiagnosticCode::PossiblyUnsupported) = code
+                else {
+                    return Err(ParseArgsError::WarningsNotAllowed(name.to_string()));
+                };
+
+                warnings.codes.push((code, level));
             } else {
-                Ok(warnings)
+                let kind = DiagnosticKind::from_str(name).map_err(|_| {
+                    ParseArgsError::Other(format!("`{name}` is not a recognized diagnostic kind"))
+                })?;
+
+                let (DiagnosticKind::Compat | DiagnosticKind::Deprecated) = kind else {
+                    return Err(ParseArgsError::WarningsNotAllowed(name.to_string()));
+                };
+
+                warnings.kinds.push((kind, level));
             }
         }
+
+        Ok(warnings)
     }
 }
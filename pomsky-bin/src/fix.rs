@@ -0,0 +1,143 @@
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use pomsky::{
+    diagnose::{Diagnostic, QuickFix, Replacement, Severity},
+    options::CompileOptions,
+    Expr,
+};
+
+use crate::{
+    args::{Args, DiagnosticSet},
+    Outcome,
+};
+
+/// How often a file is fixed and compiled again. Fixing an error may reveal
+/// errors that weren't reported before, e.g. because the parser stopped early.
+const MAX_ROUNDS: usize = 16;
+
+/// Applies the quick fixes of each file in place. Diagnostics that can't be
+/// fixed automatically are printed afterwards, and the exit code is 1 if any
+/// file still has errors.
+pub(crate) fn fix(files: &[PathBuf], args: &Args) {
+    let mut outcome = Outcome::Success;
+    for file in files {
+        outcome = outcome.max(fix_file(file, args));
+    }
+
+    if outcome != Outcome::Success {
+        efprintln!(R!"error" ": some errors could not be fixed automatically");
+        exit(outcome.exit_code());
+    }
+}
+
+fn fix_file(file: &Path, args: &Args) -> Outcome {
+    let display_path = file.to_string_lossy();
+    let mut input = match std::fs::read_to_string(file) {
+        Ok(input) => input,
+        Err(error) => {
+            crate::print_error(format!("{display_path}: {error}"));
+            return Outcome::Failure;
+        }
+    };
+
+    let options = crate::compile_options(args, Some(file));
+    let enabled_warnings = args.warnings_for(Some(file));
+
+    let mut fixed = 0;
+    let mut rounds = 0;
+    let (success, diagnostics) = loop {
+        let (success, diagnostics) = compile(&input, options, enabled_warnings);
+        let fixes = diagnostics.iter().filter_map(|d| d.fixes.first()).collect::<Vec<_>>();
+        if fixes.is_empty() || rounds == MAX_ROUNDS {
+            break (success, diagnostics);
+        }
+
+        let (output, count) = apply_fixes(&input, &fixes);
+        input = output;
+        fixed += count;
+        rounds += 1;
+    };
+
+    if fixed > 0 {
+        if let Err(error) = std::fs::write(file, &input) {
+            crate::print_error(format!("{display_path}: {error}"));
+            return Outcome::Failure;
+        }
+        match fixed {
+            1 => println!("{display_path}: applied 1 fix"),
+            _ => println!("{display_path}: applied {fixed} fixes"),
+        }
+    }
+
+    crate::print_file_diagnostics(&diagnostics, file, &display_path, &input, args.output_format);
+
+    if !success {
+        Outcome::Failure
+    } else if diagnostics.iter().any(|d| matches!(d.severity, Severity::Error)) {
+        Outcome::Denied
+    } else {
+        Outcome::Success
+    }
+}
+
+/// Returns whether compilation succeeded, and the errors and enabled warnings
+fn compile(
+    input: &str,
+    options: CompileOptions,
+    enabled_warnings: &DiagnosticSet,
+) -> (bool, Vec<Diagnostic>) {
+    let (success, diagnostics) = match Expr::parse_and_compile_detailed(input, options) {
+        Ok(compiled) => (true, compiled.warnings),
+        Err(failure) => {
+            let mut diagnostics = failure.errors;
+            diagnostics.extend(failure.warnings);
+            (false, diagnostics)
+        }
+    };
+    (success, crate::filter_warnings(diagnostics, enabled_warnings).collect())
+}
+
+/// Applies every fix that doesn't overlap with a previous fix. Returns the new
+/// source code and the number of applied fixes.
+fn apply_fixes(source_code: &str, fixes: &[&QuickFix]) -> (String, usize) {
+    let mut replacements = Vec::<&Replacement>::new();
+    let mut count = 0;
+
+    for fix in fixes {
+        let overlaps = fix
+            .replacements
+            .iter()
+            .any(|r| replacements.iter().any(|other| overlap(range(r), range(other))));
+        if !overlaps {
+            replacements.extend(&fix.replacements);
+            count += 1;
+        }
+    }
+    replacements.sort_by_key(|r| range(r).start);
+
+    let mut result = String::with_capacity(source_code.len());
+    let mut offset = 0;
+    for replacement in replacements {
+        let range = range(replacement);
+        result.push_str(&source_code[offset..range.start]);
+        result.push_str(&replacement.insert);
+        offset = range.end;
+    }
+    result.push_str(&source_code[offset..]);
+
+    (result, count)
+}
+
+fn range(replacement: &Replacement) -> Range<usize> {
+    replacement.span.range_unchecked()
+}
+
+/// Whether two ranges overlap. Insertions at the same position also overlap,
+/// since their order would be ambiguous.
+fn overlap(a: Range<usize>, b: Range<usize>) -> bool {
+    (a.start < b.end && b.start < a.end) || a.start == b.start
+}
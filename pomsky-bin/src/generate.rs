@@ -0,0 +1,253 @@
+use std::{
+    fmt::Write as _,
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use pomsky::{diagnose::Severity, options::CompileOptions, output::CompileOutput, Expr};
+
+use crate::{
+    args::{Args, Language},
+    Outcome,
+};
+
+/// A pomsky file compiled for one language
+struct Constant {
+    /// Name of the constant, derived from the file name
+    name: String,
+    /// Path of the pomsky file
+    path: String,
+    output: CompileOutput,
+}
+
+/// Compiles each file for every language, and generates a module per language
+/// with a constant for each file and its named capturing groups. The modules
+/// are written to `--out-dir`, or printed if only one language is used.
+pub(crate) fn generate(files: &[PathBuf], languages: &[Language], args: &Args) {
+    let names = files.iter().map(|file| constant_name(file)).collect::<Vec<_>>();
+    check_identifiers(
+        names.iter().zip(files).map(|(name, file)| (name.clone(), format!("'{}'", file.display()))),
+    );
+
+    let mut modules = Vec::with_capacity(languages.len());
+    let mut outcome = Outcome::Success;
+
+    for &language in languages {
+        let mut constants = Vec::with_capacity(files.len());
+
+        for (file, name) in files.iter().zip(&names) {
+            let path = file.to_string_lossy().into_owned();
+            let display_path = match languages.len() {
+                1 => path.clone(),
+                _ => format!("{path} ({})", language.name()),
+            };
+
+            let options = crate::compile_options(args, Some(file));
+            let options = CompileOptions {
+                flavor: language.flavor(),
+                // a version from `pomsky.toml` only applies to its own flavor
+                flavor_version: options.flavor_version.filter(|v| v.flavor() == language.flavor()),
+                ..options
+            };
+            match compile_file(file, &display_path, options, args) {
+                Ok(output) => constants.push(Constant { name: name.clone(), path, output }),
+                Err(file_outcome) => outcome = outcome.max(file_outcome),
+            }
+        }
+        modules.push((language, constants));
+    }
+
+    if outcome != Outcome::Success {
+        efprintln!(R!"error" ": could not generate code due to previous errors");
+        exit(outcome.exit_code());
+    }
+
+    // the group names are the same for every language
+    if let Some((_, constants)) = modules.first() {
+        check_identifiers(constants.iter().flat_map(|c| {
+            let file = (c.name.clone(), format!("'{}'", c.path));
+            let groups = named_groups(c)
+                .map(|(group, name, _)| (name, format!("group '{group}' in '{}'", c.path)));
+            std::iter::once(file).chain(groups)
+        }));
+    }
+
+    for (language, constants) in modules {
+        let code = match language {
+            Language::Rust => rust_module(&constants),
+            Language::TypeScript => typescript_module(&constants),
+            Language::Java => java_module(&constants),
+        };
+
+        match &args.out_dir {
+            Some(out_dir) => {
+                let path = out_dir.join(language.file_name());
+                if let Err(error) =
+                    std::fs::create_dir_all(out_dir).and_then(|_| std::fs::write(&path, code))
+                {
+                    crate::print_error(format!("{}: {error}", path.display()));
+                    exit(1);
+                }
+                println!("{}", path.display());
+            }
+            None => print!("{code}"),
+        }
+    }
+}
+
+/// Exits with an error if two files or capturing groups would produce the same
+/// constant. Each identifier comes with a description of where it's from.
+fn check_identifiers(identifiers: impl IntoIterator<Item = (String, String)>) {
+    let mut seen: Vec<(String, String)> = Vec::new();
+    for (name, source) in identifiers {
+        if let Some((_, prev)) = seen.iter().find(|(n, _)| *n == name) {
+            crate::print_error(format!(
+                "{prev} and {source} would both produce the constant '{name}'"
+            ));
+            exit(1);
+        }
+        seen.push((name, source));
+    }
+}
+
+fn compile_file(
+    file: &Path,
+    display_path: &str,
+    options: CompileOptions,
+    args: &Args,
+) -> Result<CompileOutput, Outcome> {
+    let input = match std::fs::read_to_string(file) {
+        Ok(input) => input,
+        Err(error) => {
+            crate::print_error(format!("{display_path}: {error}"));
+            return Err(Outcome::Failure);
+        }
+    };
+
+    let (output, diagnostics) = match Expr::parse_and_compile_detailed(&input, options) {
+        Ok(compiled) => (Ok(compiled.output), compiled.warnings),
+        Err(failure) => {
+            let mut diagnostics = failure.errors;
+            diagnostics.extend(failure.warnings);
+            (Err(Outcome::Failure), diagnostics)
+        }
+    };
+    let diagnostics =
+        crate::filter_warnings(diagnostics, args.warnings_for(Some(file))).collect::<Vec<_>>();
+
+    crate::print_file_diagnostics(&diagnostics, file, display_path, &input, args.output_format);
+    match output {
+        Ok(_) if diagnostics.iter().any(|d| matches!(d.severity, Severity::Error)) => {
+            Err(Outcome::Denied)
+        }
+        output => output,
+    }
+}
+
+/// Converts a file or group name to `SCREAMING_SNAKE_CASE`, e.g. `emailAddress`
+/// or `email-address` to `EMAIL_ADDRESS`
+fn screaming_snake_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len() + 4);
+    let mut prev_is_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if prev_is_lower && c.is_ascii_uppercase() {
+                result.push('_');
+            }
+            result.push(c.to_ascii_uppercase());
+            prev_is_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            result.push('_');
+            prev_is_lower = false;
+        }
+    }
+    if !result.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        result.insert(0, '_');
+    }
+    result
+}
+
+fn constant_name(file: &Path) -> String {
+    let stem = file.file_stem().unwrap_or(file.as_os_str());
+    screaming_snake_case(&stem.to_string_lossy())
+}
+
+/// Returns the name and index of each named capturing group
+fn named_groups(constant: &Constant) -> impl Iterator<Item = (&str, String, u32)> {
+    constant.output.groups.iter().filter_map(move |group| {
+        let name = group.name.as_deref()?;
+        Some((name, format!("{}_{}", constant.name, screaming_snake_case(name)), group.index))
+    })
+}
+
+fn header() -> String {
+    format!("// Generated by pomsky {}. Do not edit.\n", env!("CARGO_PKG_VERSION"))
+}
+
+/// Escapes `*/` in a path, so it can be used in a `/** */` doc comment
+fn escape_doc_comment(path: &str) -> String {
+    path.replace("*/", "*&#47;")
+}
+
+fn rust_module(constants: &[Constant]) -> String {
+    let mut code = header();
+    for c in constants {
+        let literal = c.output.to_literal(Language::Rust.flavor());
+        writeln!(code, "\n/// Compiled from `{}`", c.path).unwrap();
+        writeln!(code, "pub const {}: &str = {literal};", c.name).unwrap();
+        for (group, name, index) in named_groups(c) {
+            writeln!(code, "/// Index of the capturing group `{group}` in [`{}`]", c.name).unwrap();
+            writeln!(code, "pub const {name}: usize = {index};").unwrap();
+        }
+    }
+    code
+}
+
+fn typescript_module(constants: &[Constant]) -> String {
+    let mut code = header();
+    for c in constants {
+        let literal = c.output.to_literal(Language::TypeScript.flavor());
+        writeln!(code, "\n/** Compiled from `{}` */", escape_doc_comment(&c.path)).unwrap();
+        writeln!(code, "export const {} = {literal};", c.name).unwrap();
+        for (group, name, index) in named_groups(c) {
+            writeln!(code, "/** Index of the capturing group `{group}` in {{@link {}}} */", c.name)
+                .unwrap();
+            writeln!(code, "export const {name} = {index};").unwrap();
+        }
+    }
+    code
+}
+
+fn java_module(constants: &[Constant]) -> String {
+    let mut code = header();
+    code.push_str("\nimport java.util.regex.Pattern;\n\n");
+    code.push_str("public final class Patterns {\n    private Patterns() {}\n");
+    for c in constants {
+        let literal = c.output.to_literal(Language::Java.flavor());
+        let flags = &c.output.flags;
+        let flags = if flags.required.contains('U') || flags.recommended.contains('U') {
+            ", Pattern.UNICODE_CHARACTER_CLASS"
+        } else {
+            ""
+        };
+        writeln!(code, "\n    /** Compiled from {{@code {}}} */", escape_doc_comment(&c.path))
+            .unwrap();
+        writeln!(
+            code,
+            "    public static final Pattern {} = Pattern.compile({literal}{flags});",
+            c.name
+        )
+        .unwrap();
+        for (group, name, index) in named_groups(c) {
+            writeln!(
+                code,
+                "    /** Index of the capturing group {{@code {group}}} in {{@link #{}}} */",
+                c.name
+            )
+            .unwrap();
+            writeln!(code, "    public static final int {name} = {index};").unwrap();
+        }
+    }
+    code.push_str("}\n");
+    code
+}
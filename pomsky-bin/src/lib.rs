@@ -1,5 +1,6 @@
 mod result;
 
 pub use result::{
-    CompilationResult, Diagnostic, Kind, QuickFix, Replacement, Severity, Span, Timings, Version,
+    CompilationResult, Diagnostic, Flags, FlavorCheckResult, Group, Kind, QuickFix, Region,
+    Replacement, SarifLog, Severity, Span, Timings, Version,
 };
@@ -1,18 +1,30 @@
-use std::{io, io::Write as _, process::exit, time::Instant};
+use std::{
+    collections::BTreeMap,
+    io,
+    io::Write as _,
+    path::{Path, PathBuf},
+    process::exit,
+    time::Instant,
+};
 
 use pomsky::{
     diagnose::{Diagnostic, Severity},
     options::{CompileOptions, RegexFlavor},
+    output::CompileOutput,
     Expr,
 };
 
 #[macro_use]
 mod format;
 mod args;
+mod fix;
+mod generate;
+mod repl;
 mod result;
+mod watch;
 
-use args::{Args, DiagnosticSet, Input};
-use result::CompilationResult;
+use args::{Args, DiagnosticSet, Emit, Input, OutputFormat, WarningLevel};
+use result::{CompilationResult, FlavorCheckResult, Region, SarifLog};
 
 pub fn main() {
     let args = match args::parse_args() {
@@ -28,33 +40,83 @@ pub fn main() {
     };
 
     match &args.input {
-        Input::Value(input) => compile(input, &args),
+        Input::Value(input) => run(input, None, &args),
         Input::File(path) => match std::fs::read_to_string(path) {
-            Ok(input) => compile(&input, &args),
+            Ok(input) => run(&input, Some(path), &args),
             Err(error) => {
-                print_diagnostic(
+                report(
                     &Diagnostic::ad_hoc(Severity::Error, None, error.to_string(), None),
+                    Some(path),
                     None,
+                    args.output_format,
                 );
                 exit(3);
             }
         },
+        Input::Files(paths) => compile_files(paths, &args),
+        Input::Watch(paths) => watch::watch(paths, &args),
+        Input::Fix(files) => fix::fix(files, &args),
+        Input::Repl => repl::repl(&args),
+        Input::Generate { files, languages } => generate::generate(files, languages, &args),
     }
 }
 
-fn compile(input: &str, args: &Args) {
-    let start = Instant::now();
+/// The result of a compilation, which determines the exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Outcome {
+    Success,
+    /// Compilation succeeded, but a denied warning was emitted
+    Denied,
+    Failure,
+}
 
-    let options = CompileOptions {
-        flavor: args.flavor.unwrap_or(RegexFlavor::Pcre),
-        max_range_size: 12,
-        allowed_features: args.allowed_features,
-    };
+impl Outcome {
+    fn exit_code(self) -> i32 {
+        match self {
+            Outcome::Success => 0,
+            Outcome::Failure => 1,
+            Outcome::Denied => 4,
+        }
+    }
+}
+
+fn run(input: &str, path: Option<&Path>, args: &Args) {
+    if args.check_all_flavors {
+        check_all_flavors(input, path, args);
+    } else {
+        compile(input, path, args);
+    }
+}
+
+/// Returns the options for compiling the file at `path`, or an expression that
+/// isn't from a file if `path` is `None`
+fn compile_options(args: &Args, path: Option<&Path>) -> CompileOptions {
+    CompileOptions {
+        flavor: args.flavor_for(path).unwrap_or(RegexFlavor::Pcre),
+        flavor_version: args.flavor_version_for(path),
+        max_range_size: args.max_range_size_for(path),
+        allowed_features: args.allowed_features_for(path),
+        ..Default::default()
+    }
+}
+
+/// Replaces the regex with a string or regex literal if `--emit=literal` is used
+fn emit(mut output: CompileOutput, options: CompileOptions, args: &Args) -> CompileOutput {
+    if args.emit == Emit::Literal {
+        output.regex = output.to_literal(options.flavor);
+    }
+    output
+}
+
+fn compile(input: &str, path: Option<&Path>, args: &Args) {
+    let start = Instant::now();
+    let options = compile_options(args, path);
+    let enabled_warnings = args.warnings_for(path);
 
     let (parsed, warnings) = match Expr::parse(input) {
         (Some(res), warnings) => (res, warnings),
         (None, err) => {
-            print_parse_errors(err, Some(input), start.elapsed().as_micros(), args.json);
+            print_parse_errors(err, path, Some(input), start.elapsed().as_micros(), args);
             exit(1);
         }
     };
@@ -65,78 +127,378 @@ fn compile(input: &str, args: &Args) {
         eprintln!("{parsed:#?}\n");
     }
 
-    if !args.json {
-        print_warnings(&warnings, args, Some(input));
+    let format = args.output_format;
+    let mut denied = false;
+    if !format.is_structured() {
+        denied |= print_warnings(&warnings, enabled_warnings, path, Some(input), format);
     }
 
-    let compiled = match parsed.compile(input, options) {
-        (Some(res), compile_warnings) => {
-            if args.json {
-                warnings.extend(compile_warnings);
+    let compiled = match parsed.compile_detailed(input, options) {
+        Ok(compiled) => {
+            if format.is_structured() {
+                warnings.extend(compiled.warnings);
             } else {
-                print_warnings(&compile_warnings, args, Some(input));
+                denied |=
+                    print_warnings(&compiled.warnings, enabled_warnings, path, Some(input), format);
             }
 
-            res
+            emit(compiled.output, options, args)
         }
-        (None, errors) => {
-            if args.json {
-                CompilationResult::error(start.elapsed().as_micros())
-                    .with_diagnostics(errors, Some(input))
-                    .with_diagnostics(
-                        warnings.into_iter().filter_map(|w| {
-                            if args.warnings.is_enabled(w.kind) {
-                                Some(w)
-                            } else {
-                                None
-                            }
-                        }),
-                        Some(input),
-                    )
-                    .output_json();
+        Err(failure) => {
+            if format.is_structured() {
+                warnings.extend(failure.warnings);
+                let result = CompilationResult::error(start.elapsed().as_micros())
+                    .with_diagnostics(failure.errors, Some(input))
+                    .with_diagnostics(filter_warnings(warnings, enabled_warnings), Some(input));
+                output_result(&result, path, Some(input), format);
             } else {
-                for err in &errors {
-                    print_diagnostic(err, Some(input));
+                print_warnings(&failure.warnings, enabled_warnings, path, Some(input), format);
+                for err in &failure.errors {
+                    report(err, path, Some(input), format);
                 }
             }
             std::process::exit(1);
         }
     };
 
-    if args.json {
-        CompilationResult::success(compiled, start.elapsed().as_micros())
-            .with_diagnostics(
-                warnings.into_iter().filter_map(|w| {
-                    if args.warnings.is_enabled(w.kind) {
-                        Some(w)
-                    } else {
-                        None
-                    }
-                }),
-                Some(input),
-            )
-            .output_json();
+    if format.is_structured() {
+        let warnings = filter_warnings(warnings, enabled_warnings).collect::<Vec<_>>();
+        let time = start.elapsed().as_micros();
+        if warnings.iter().any(|w| matches!(w.severity, Severity::Error)) {
+            let result = CompilationResult::error(time).with_diagnostics(warnings, Some(input));
+            output_result(&result, path, Some(input), format);
+            exit(Outcome::Denied.exit_code());
+        }
+        let result = CompilationResult::success(compiled.regex, time)
+            .with_groups(compiled.groups)
+            .with_flags(compiled.flags)
+            .with_diagnostics(warnings, Some(input));
+        output_result(&result, path, Some(input), format);
+    } else if denied {
+        efprintln!(R!"error" ": could not compile expression due to denied warnings");
+        exit(Outcome::Denied.exit_code());
     } else if args.no_new_line {
-        print!("{compiled}");
+        print!("{}", compiled.regex);
         io::stdout().flush().unwrap();
     } else {
-        println!("{compiled}");
+        println!("{}", compiled.regex);
+    }
+}
+
+fn check_all_flavors(input: &str, path: Option<&Path>, args: &Args) {
+    let start = Instant::now();
+    let options = compile_options(args, path);
+    let enabled_warnings = args.warnings_for(path);
+
+    let (parsed, warnings) = match Expr::parse(input) {
+        (Some(res), warnings) => (res, warnings),
+        (None, err) => {
+            print_parse_errors(err, path, Some(input), start.elapsed().as_micros(), args);
+            exit(1);
+        }
+    };
+    let warnings = warnings.collect::<Vec<_>>();
+
+    if args.debug {
+        eprintln!("======================== debug ========================");
+        eprintln!("{parsed:#?}\n");
+    }
+
+    let checks = parsed.check_all_flavors(input, options);
+    let all_succeeded = checks.iter().all(|check| check.result.is_ok());
+    let is_enabled = |w: &&Diagnostic| enabled_warnings.is_enabled(w);
+    let is_denied = |w: &Diagnostic| enabled_warnings.level(w) == WarningLevel::Deny;
+    let any_denied = warnings.iter().any(is_denied)
+        || checks.iter().any(|check| match &check.result {
+            Ok(compiled) => compiled.warnings.iter().any(is_denied),
+            Err(_) => false,
+        });
+
+    if args.output_format == OutputFormat::Json {
+        let results = checks
+            .into_iter()
+            .map(|check| {
+                let result = match check.result {
+                    Ok(compiled) => CompilationResult::success(
+                        compiled.output.regex,
+                        compiled.timings.total().as_micros(),
+                    )
+                    .with_groups(compiled.output.groups)
+                    .with_flags(compiled.output.flags)
+                    .with_diagnostics(
+                        filter_warnings(
+                            warnings.iter().chain(&compiled.warnings).cloned(),
+                            enabled_warnings,
+                        ),
+                        Some(input),
+                    ),
+                    Err(failure) => CompilationResult::error(failure.timings.total().as_micros())
+                        .with_diagnostics(failure.errors, Some(input))
+                        .with_diagnostics(
+                            filter_warnings(
+                                warnings.iter().chain(&failure.warnings).cloned(),
+                                enabled_warnings,
+                            ),
+                            Some(input),
+                        ),
+                };
+                FlavorCheckResult {
+                    flavor: args::flavor_name(check.flavor).to_string(),
+                    unsupported_feature: check.unsupported_feature.map(|f| f.name().to_string()),
+                    result,
+                }
+            })
+            .collect::<Vec<_>>();
+        FlavorCheckResult::output_json(&results);
+    } else {
+        print_warnings(&warnings, enabled_warnings, path, Some(input), args.output_format);
+
+        println!("{:<14}RESULT", "FLAVOR");
+        for check in &checks {
+            let flavor = args::flavor_name(check.flavor);
+            let result = match (&check.result, check.unsupported_feature) {
+                (Ok(compiled), _) => match compiled.warnings.iter().filter(is_enabled).count() {
+                    0 => "ok".to_string(),
+                    1 => "ok (1 warning)".to_string(),
+                    n => format!("ok ({n} warnings)"),
+                },
+                (Err(_), Some(feature)) => format!("unsupported: {}", feature.name()),
+                (Err(failure), None) => match failure.errors.first() {
+                    Some(error) => format!("error: {}", error.msg),
+                    None => "error".to_string(),
+                },
+            };
+            println!("{flavor:<14}{result}");
+        }
+    }
+
+    if !all_succeeded {
+        exit(Outcome::Failure.exit_code());
+    } else if any_denied {
+        exit(Outcome::Denied.exit_code());
+    }
+}
+
+fn compile_files(paths: &[PathBuf], args: &Args) {
+    if let Some(out_dir) = &args.out_dir {
+        if let Err(error) = check_out_files(out_dir, paths) {
+            print_error(error);
+            exit(Outcome::Failure.exit_code());
+        }
+    }
+    let outcome = compile_each(paths, args);
+    if outcome != Outcome::Success {
+        exit(outcome.exit_code());
+    }
+}
+
+/// Compiles each file separately. With `--json`, a single object is printed that maps each
+/// path to its result, and with `--output-format=sarif`, a SARIF log with the diagnostics
+/// of all files. Otherwise, the regex of each file is printed after its path, or written
+/// to the `--out-dir` directory.
+///
+/// Returns the worst outcome of all files.
+fn compile_each(paths: &[PathBuf], args: &Args) -> Outcome {
+    let format = args.output_format;
+    let mut results = BTreeMap::new();
+    let mut sarif = SarifLog::default();
+    let mut outcome = Outcome::Success;
+
+    for path in paths {
+        let display_path = path.to_string_lossy().into_owned();
+
+        let input = match std::fs::read_to_string(path) {
+            Ok(input) => input,
+            Err(error) => {
+                outcome = Outcome::Failure;
+                let diagnostic = Diagnostic::ad_hoc(
+                    Severity::Error,
+                    None,
+                    format!("{display_path}: {error}"),
+                    None,
+                );
+                if format.is_structured() {
+                    let result = CompilationResult::error(0).with_diagnostics([diagnostic], None);
+                    if format == OutputFormat::Sarif {
+                        sarif.add(Some(&display_path), &result, None);
+                    } else {
+                        results.insert(display_path, result);
+                    }
+                } else {
+                    report(&diagnostic, None, None, format);
+                }
+                continue;
+            }
+        };
+
+        let options = compile_options(args, Some(path));
+        let enabled_warnings = args.warnings_for(Some(path));
+        let (result, diagnostics) = match Expr::parse_and_compile_detailed(&input, options) {
+            Ok(compiled) => {
+                let time = compiled.timings.total().as_micros();
+                let output = emit(compiled.output, options, args);
+                let result = CompilationResult::success(output.regex, time)
+                    .with_groups(output.groups)
+                    .with_flags(output.flags);
+                (Ok(result), compiled.warnings)
+            }
+            Err(failure) => {
+                let time = failure.timings.total().as_micros();
+                let mut diagnostics = failure.errors;
+                diagnostics.extend(failure.warnings);
+                (Err(CompilationResult::error(time)), diagnostics)
+            }
+        };
+        let mut diagnostics = filter_warnings(diagnostics, enabled_warnings).collect::<Vec<_>>();
+
+        // the file fails if a warning is denied, but with a different exit code
+        let mut file_outcome = Outcome::Failure;
+        let result = match result {
+            Ok(result) if diagnostics.iter().any(|d| matches!(d.severity, Severity::Error)) => {
+                file_outcome = Outcome::Denied;
+                Err(CompilationResult::error(result.timings.all))
+            }
+            result => result,
+        };
+
+        let mut out_path = None;
+        if let (Some(out_dir), Ok(result)) = (&args.out_dir, &result) {
+            let path = out_file(out_dir, path);
+            let regex = result.output.as_deref().unwrap_or_default();
+            match std::fs::create_dir_all(out_dir).and_then(|_| std::fs::write(&path, regex)) {
+                Ok(()) => out_path = Some(path),
+                Err(error) => diagnostics.push(Diagnostic::ad_hoc(
+                    Severity::Error,
+                    None,
+                    format!("{}: {error}", path.to_string_lossy()),
+                    None,
+                )),
+            }
+        }
+        let result = match result {
+            Ok(result) if args.out_dir.is_some() && out_path.is_none() => {
+                file_outcome = Outcome::Failure;
+                Err(CompilationResult::error(result.timings.all))
+            }
+            result => result,
+        };
+
+        if format.is_structured() {
+            let result = result.unwrap_or_else(|result| {
+                outcome = outcome.max(file_outcome);
+                result
+            });
+            let result = result.with_diagnostics(diagnostics, Some(&input));
+            if format == OutputFormat::Sarif {
+                sarif.add(Some(&display_path), &result, Some(&input));
+            } else {
+                results.insert(display_path, result);
+            }
+            continue;
+        }
+
+        print_file_diagnostics(&diagnostics, path, &display_path, &input, format);
+
+        match (result, out_path) {
+            (Ok(_), Some(out_path)) => println!("{display_path} -> {}", out_path.display()),
+            (Ok(result), None) => println!("{display_path}: {}", result.output.unwrap_or_default()),
+            (Err(_), _) => {
+                outcome = outcome.max(file_outcome);
+                efprintln!(R!"error" ": could not compile " {&display_path});
+            }
+        }
+    }
+
+    match format {
+        OutputFormat::Json => CompilationResult::output_json_map(&results),
+        OutputFormat::Sarif => sarif.output(),
+        OutputFormat::Human | OutputFormat::Gnu => {}
+    }
+
+    outcome
+}
+
+/// Prints the diagnostics of a file. In the human-readable format, they are
+/// preceded by a note containing its path.
+fn print_file_diagnostics(
+    diagnostics: &[Diagnostic],
+    path: &Path,
+    display_path: &str,
+    source_code: &str,
+    format: OutputFormat,
+) {
+    if !diagnostics.is_empty() {
+        if format == OutputFormat::Human {
+            efprintln!(C!"note" ": in " {display_path});
+        }
+        for diagnostic in diagnostics {
+            report(diagnostic, Some(path), Some(source_code), format);
+        }
+    }
+}
+
+/// Returns the path in `out_dir` where the regex compiled from `path` is written to, e.g.
+/// `out_dir/email.regex` for `patterns/email.pom`
+fn out_file(out_dir: &Path, path: &Path) -> PathBuf {
+    let mut name = path.file_stem().unwrap_or(path.as_os_str()).to_os_string();
+    name.push(".regex");
+    out_dir.join(name)
+}
+
+/// Returns an error if two different files would be written to the same path
+/// in `out_dir`, e.g. `a/email.pom` and `b/email.pom`
+fn check_out_files(out_dir: &Path, paths: &[PathBuf]) -> Result<(), String> {
+    let out_files = paths.iter().map(|path| out_file(out_dir, path)).collect::<Vec<_>>();
+    for (i, out_file) in out_files.iter().enumerate() {
+        let duplicate = out_files[..i]
+            .iter()
+            .zip(paths)
+            .position(|(other, other_path)| other == out_file && other_path != &paths[i]);
+        if let Some(j) = duplicate {
+            return Err(format!(
+                "'{}' and '{}' would both be written to '{}'",
+                paths[j].display(),
+                paths[i].display(),
+                out_file.display(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Prints a single compilation result with `--json` or `--output-format=sarif`
+fn output_result(
+    result: &CompilationResult,
+    path: Option<&Path>,
+    source_code: Option<&str>,
+    format: OutputFormat,
+) {
+    match format {
+        OutputFormat::Sarif => {
+            let mut sarif = SarifLog::default();
+            sarif.add(path.map(|path| path.to_string_lossy()).as_deref(), result, source_code);
+            sarif.output();
+        }
+        _ => result.output_json(),
     }
 }
 
 fn print_parse_errors(
     mut diagnostics: impl Iterator<Item = Diagnostic>,
+    path: Option<&Path>,
     source_code: Option<&str>,
     time: u128,
-    json: bool,
+    args: &Args,
 ) {
-    if json {
-        CompilationResult::error(time).with_diagnostics(diagnostics, source_code).output_json();
+    let format = args.output_format;
+    if format.is_structured() {
+        let result = CompilationResult::error(time).with_diagnostics(diagnostics, source_code);
+        output_result(&result, path, source_code, format);
     } else {
         let mut len = 0;
         for d in (&mut diagnostics).take(8) {
             len += 1;
-            print_diagnostic(&d, source_code);
+            report(&d, path, source_code, format);
         }
 
         len += diagnostics.count();
@@ -154,21 +516,26 @@ fn print_parse_errors(
     }
 }
 
-fn print_warnings(warnings: &[Diagnostic], args: &Args, source_code: Option<&str>) {
-    if matches!(&args.warnings, DiagnosticSet::Enabled(set) if set.is_empty()) {
-        return;
-    }
-
+/// Prints the warnings that aren't allowed. Denied warnings are printed as errors.
+///
+/// Returns `true` if any warning was denied.
+fn print_warnings(
+    warnings: &[Diagnostic],
+    enabled: &DiagnosticSet,
+    path: Option<&Path>,
+    source_code: Option<&str>,
+    format: OutputFormat,
+) -> bool {
     let mut len = 0;
+    let mut denied = false;
 
-    for diagnostic in warnings {
-        if args.warnings.is_enabled(diagnostic.kind) {
-            len += 1;
-            match len {
-                1..=8 => print_diagnostic(diagnostic, source_code),
-                9 => efprintln!(C!"note" ": some warnings were omitted"),
-                _ => {}
-            }
+    for diagnostic in filter_warnings(warnings.iter().cloned(), enabled) {
+        denied |= matches!(diagnostic.severity, Severity::Error);
+        len += 1;
+        match len {
+            1..=8 => report(&diagnostic, path, source_code, format),
+            9 => efprintln!(C!"note" ": some warnings were omitted"),
+            _ => {}
         }
     }
 
@@ -176,6 +543,72 @@ fn print_warnings(warnings: &[Diagnostic], args: &Args, source_code: Option<&str
         let len = len.to_string();
         efprintln!(Y!"warning" ": pomsky generated " {&len} " warnings");
     }
+    denied
+}
+
+/// Removes warnings that are allowed, and turns warnings that are denied into errors
+fn filter_warnings<'a>(
+    diagnostics: impl IntoIterator<Item = Diagnostic> + 'a,
+    enabled: &'a DiagnosticSet,
+) -> impl Iterator<Item = Diagnostic> + 'a {
+    diagnostics.into_iter().filter_map(|mut diagnostic| {
+        if matches!(diagnostic.severity, Severity::Error) {
+            return Some(diagnostic);
+        }
+        match enabled.level(&diagnostic) {
+            WarningLevel::Allow => None,
+            WarningLevel::Warn => Some(diagnostic),
+            WarningLevel::Deny => {
+                diagnostic.severity = Severity::Error;
+                Some(diagnostic)
+            }
+        }
+    })
+}
+
+fn print_error(msg: String) {
+    print_diagnostic(&Diagnostic::ad_hoc(Severity::Error, None, msg, None), None);
+}
+
+/// Prints a diagnostic in the human-readable format, or in the GNU format if
+/// `--output-format=gnu` is used
+fn report(
+    diagnostic: &Diagnostic,
+    path: Option<&Path>,
+    source_code: Option<&str>,
+    format: OutputFormat,
+) {
+    match format {
+        OutputFormat::Gnu => print_gnu_diagnostic(diagnostic, path, source_code),
+        _ => print_diagnostic(diagnostic, source_code),
+    }
+}
+
+/// Prints a diagnostic as `file:line:column: severity: message [code]`. The help
+/// is printed as a note on the next line. Diagnostics without a location are
+/// prefixed with the file, or with `pomsky` if there is no file.
+fn print_gnu_diagnostic(diagnostic: &Diagnostic, path: Option<&Path>, source_code: Option<&str>) {
+    let file = match path {
+        Some(path) => path.to_string_lossy(),
+        None => "<input>".into(),
+    };
+    let location = match (source_code, diagnostic.span.range()) {
+        (Some(source_code), Some(range)) => {
+            let region = Region::new(source_code, range);
+            format!("{file}:{}:{}", region.start_line, region.start_column)
+        }
+        _ if path.is_some() => file.into_owned(),
+        _ => "pomsky".to_string(),
+    };
+
+    let severity: &str = diagnostic.severity.into();
+    match diagnostic.code {
+        Some(code) => eprintln!("{location}: {severity}: {} [{code}]", diagnostic.msg),
+        None => eprintln!("{location}: {severity}: {}", diagnostic.msg),
+    }
+    if let Some(help) = &diagnostic.help {
+        eprintln!("{location}: note: {help}");
+    }
 }
 
 fn print_diagnostic(diagnostic: &Diagnostic, source_code: Option<&str>) {
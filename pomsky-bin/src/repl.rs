@@ -0,0 +1,284 @@
+use std::io::{self, BufRead, Write as _};
+
+use atty::Stream;
+use pomsky::{
+    options::{CompileOptions, FlavorVersion, RegexFlavor},
+    Expr,
+};
+use regex::Regex;
+
+use crate::args::{self, Args, OutputFormat};
+
+const HELP: &str = "\
+Enter a pomsky expression to compile it. Variables declared on their own line,
+such as `let x = 'test';`, are available in all following expressions.
+
+Commands:
+    :flavor <FLAVOR>  Switch to a different regex flavor, e.g. `js` or `js@es2018`
+    :test <TEXT>      Match the last expression against a text, which can be quoted
+    :clear            Remove all variables and the last expression
+    :help             Print this help
+    :quit             Exit the REPL";
+
+/// A variable declared in the REPL
+struct Binding {
+    name: String,
+    source: String,
+}
+
+/// State of an interactive session, which is kept across lines
+struct Repl<'a> {
+    args: &'a Args,
+    flavor: RegexFlavor,
+    flavor_version: Option<FlavorVersion>,
+    /// Variables that are declared before every expression
+    bindings: Vec<Binding>,
+    /// The last expression that compiled successfully
+    expression: Option<String>,
+}
+
+/// Runs the REPL until the user enters `:quit` or stdin is closed.
+pub(crate) fn repl(args: &Args) {
+    let is_interactive = atty::is(Stream::Stdin);
+    let mut repl = Repl {
+        args,
+        flavor: args.flavor.unwrap_or(RegexFlavor::Pcre),
+        flavor_version: args.flavor_version,
+        bindings: vec![],
+        expression: None,
+    };
+
+    if is_interactive {
+        println!("pomsky {} (type :help for help)", env!("CARGO_PKG_VERSION"));
+    }
+
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        if is_interactive {
+            print!("> ");
+            io::stdout().flush().unwrap();
+        }
+
+        match lines.next() {
+            Some(Ok(line)) => {
+                if !repl.eval(line.trim()) {
+                    break;
+                }
+            }
+            Some(Err(error)) => {
+                crate::print_error(error.to_string());
+                break;
+            }
+            None => break,
+        }
+    }
+}
+
+impl Repl<'_> {
+    /// Evaluates a line. Returns `false` if the REPL should exit.
+    fn eval(&mut self, line: &str) -> bool {
+        if let Some((command, rest)) = parse_command(line) {
+            match command {
+                "q" | "quit" | "exit" => return false,
+                "h" | "help" => println!("{HELP}"),
+                "flavor" => self.set_flavor(rest),
+                "test" => self.test(rest),
+                _ => {
+                    self.bindings.clear();
+                    self.expression = None;
+                }
+            }
+        } else if let Some(name) = binding_name(line) {
+            // declaring a variable again replaces it
+            let index = self.bindings.iter().position(|b| b.name == name);
+            let previous = index.map(|i| self.bindings.remove(i));
+
+            if self.compile(line, self.flavor, true).is_some() {
+                self.bindings.push(Binding { name: name.to_string(), source: line.to_string() });
+            } else if let (Some(i), Some(previous)) = (index, previous) {
+                self.bindings.insert(i, previous);
+            }
+        } else if !line.is_empty() {
+            if let Some(regex) = self.compile(line, self.flavor, true) {
+                println!("{regex}");
+                self.expression = Some(line.to_string());
+            }
+        }
+        true
+    }
+
+    fn set_flavor(&mut self, flavor: &str) {
+        if flavor.is_empty() {
+            match self.flavor_version {
+                Some(version) => {
+                    println!("{}@{}", args::flavor_name(self.flavor), args::version_name(version))
+                }
+                None => println!("{}", args::flavor_name(self.flavor)),
+            }
+            return;
+        }
+
+        match args::parse_flavor(flavor.into()) {
+            Ok((flavor, version)) => {
+                self.flavor = flavor;
+                self.flavor_version = version;
+                if let Some(expression) = &self.expression {
+                    if let Some(regex) = self.compile(expression, flavor, true) {
+                        println!("{regex}");
+                    }
+                }
+            }
+            Err(error) => crate::print_error(error.to_string()),
+        }
+    }
+
+    /// Compiles the last expression for the `regex` crate, and prints all
+    /// matches in the text, along with their capturing groups
+    fn test(&self, text: &str) {
+        let Some(expression) = &self.expression else {
+            crate::print_error("there is no expression to test, enter an expression first".into());
+            return;
+        };
+        let Some(regex) = self.compile(expression, RegexFlavor::Rust, false) else {
+            return;
+        };
+        let regex = match Regex::new(&regex) {
+            Ok(regex) => regex,
+            Err(error) => {
+                crate::print_error(error.to_string());
+                return;
+            }
+        };
+
+        let text = unquote(text);
+        let names = regex.capture_names().collect::<Vec<_>>();
+        let mut count = 0;
+
+        for captures in regex.captures_iter(&text) {
+            count += 1;
+            let m = captures.get(0).unwrap();
+            println!("match {count} at {}..{}: {:?}", m.start(), m.end(), m.as_str());
+
+            for (i, group) in captures.iter().enumerate().skip(1) {
+                let name = match names[i] {
+                    Some(name) => format!("{i} ({name})"),
+                    None => i.to_string(),
+                };
+                match group {
+                    Some(group) => println!("  {name}: {:?}", group.as_str()),
+                    None => println!("  {name}: no match"),
+                }
+            }
+        }
+
+        if count == 0 {
+            println!("no match");
+        }
+    }
+
+    /// Compiles an expression with all variables declared before it, and
+    /// prints the diagnostics. Warnings are only printed if `print_warnings`
+    /// is true.
+    fn compile(
+        &self,
+        expression: &str,
+        flavor: RegexFlavor,
+        print_warnings: bool,
+    ) -> Option<String> {
+        let mut source = String::new();
+        for binding in &self.bindings {
+            source.push_str(&binding.source);
+            source.push('\n');
+        }
+        source.push_str(expression);
+
+        let options = CompileOptions {
+            flavor,
+            // `:test` compiles for the `regex` crate, where the version doesn't apply
+            flavor_version: self.flavor_version.filter(|v| v.flavor() == flavor),
+            ..crate::compile_options(self.args, None)
+        };
+        match Expr::parse_and_compile_detailed(&source, options) {
+            Ok(compiled) => {
+                if print_warnings {
+                    crate::print_warnings(
+                        &compiled.warnings,
+                        &self.args.warnings,
+                        None,
+                        Some(&source),
+                        OutputFormat::Human,
+                    );
+                }
+                Some(compiled.output.regex)
+            }
+            Err(failure) => {
+                if print_warnings {
+                    crate::print_warnings(
+                        &failure.warnings,
+                        &self.args.warnings,
+                        None,
+                        Some(&source),
+                        OutputFormat::Human,
+                    );
+                }
+                for error in &failure.errors {
+                    crate::print_diagnostic(error, Some(&source));
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Returns the command and its argument if the line is a command. Other lines
+/// starting with `:` are capturing groups, e.g. `:test('a')`.
+fn parse_command(line: &str) -> Option<(&str, &str)> {
+    let line = line.strip_prefix(':')?;
+    let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    match command {
+        "q" | "quit" | "exit" | "h" | "help" | "flavor" | "test" | "clear" => {
+            Some((command, rest.trim()))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the variable name if the line only declares a variable, e.g.
+/// `let x = 'test';`
+fn binding_name(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("let ")?;
+    if !line.ends_with(';') {
+        return None;
+    }
+    let (name, _) = rest.split_once('=')?;
+    Some(name.trim())
+}
+
+/// Removes quotes around the text and resolves the escapes `\"`, `\\`, `\n`
+/// and `\t` in it. Text without quotes is returned as is.
+fn unquote(text: &str) -> String {
+    let inner = match text.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+        Some(inner) => inner,
+        None => return text.to_string(),
+    };
+
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some(c @ ('"' | '\\')) => result.push(c),
+            Some(c) => {
+                result.push('\\');
+                result.push(c);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
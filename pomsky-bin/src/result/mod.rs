@@ -1,10 +1,16 @@
-use std::fmt;
+use std::{collections::BTreeMap, fmt};
 
-use pomsky::diagnose::{DiagnosticCode, DiagnosticKind};
+use pomsky::{
+    diagnose::{DiagnosticCode, DiagnosticKind},
+    output::{GroupInfo, RegexFlags},
+};
 use serde::{Deserialize, Serialize};
 
+mod sarif;
 mod serde_code;
 
+pub use sarif::SarifLog;
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct CompilationResult {
     /// Schema version
@@ -18,6 +24,16 @@ pub struct CompilationResult {
     pub output: Option<String>,
     /// Array of errors and warnings
     pub diagnostics: Vec<Diagnostic>,
+    /// Array of capturing groups in the compiled regex, ordered by their index
+    ///
+    /// Empty if compilation failed
+    #[serde(default)]
+    pub groups: Vec<Group>,
+    /// Flags that must or should be passed to the regex engine
+    ///
+    /// Both are empty if compilation failed
+    #[serde(default)]
+    pub flags: Flags,
     /// Compilation time
     pub timings: Timings,
 }
@@ -35,6 +51,8 @@ impl CompilationResult {
             success: true,
             output: Some(output),
             diagnostics: vec![],
+            groups: vec![],
+            flags: Flags::default(),
             timings: Timings::from_micros(time_micros),
         }
     }
@@ -45,6 +63,8 @@ impl CompilationResult {
             success: false,
             output: None,
             diagnostics: vec![],
+            groups: vec![],
+            flags: Flags::default(),
             timings: Timings::from_micros(time_micros),
         }
     }
@@ -58,12 +78,54 @@ impl CompilationResult {
         self
     }
 
+    pub fn with_groups(mut self, groups: impl IntoIterator<Item = GroupInfo>) -> Self {
+        self.groups.extend(groups.into_iter().map(Group::from));
+        self
+    }
+
+    pub fn with_flags(mut self, flags: RegexFlags) -> Self {
+        self.flags = flags.into();
+        self
+    }
+
     pub fn output_json(&self) {
         match serde_json::to_string(self) {
             Ok(string) => println!("{string}"),
             Err(e) => eprintln!("{e}"),
         }
     }
+
+    /// Prints the results of compiling multiple files as a single JSON object,
+    /// mapping each path to its result
+    pub fn output_json_map(results: &BTreeMap<String, CompilationResult>) {
+        match serde_json::to_string(results) {
+            Ok(string) => println!("{string}"),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+}
+
+/// The result for one flavor when compiling with `--check-all-flavors`
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct FlavorCheckResult {
+    /// Name of the regex flavor, as accepted by `--flavor`
+    pub flavor: String,
+    /// Regex feature that isn't supported by this flavor, absent if
+    /// compilation succeeded or failed for a different reason
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unsupported_feature: Option<String>,
+    /// Compilation result for this flavor
+    #[serde(flatten)]
+    pub result: CompilationResult,
+}
+
+impl FlavorCheckResult {
+    pub fn output_json(results: &[FlavorCheckResult]) {
+        match serde_json::to_string(results) {
+            Ok(string) => println!("{string}"),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -90,7 +152,7 @@ pub struct Diagnostic {
     pub help: Vec<String>,
     /// Automatically applicable fixes
     ///
-    /// Currently unused and guaranteed to be empty
+    /// Currently guaranteed to contain at most 1 fix
     pub fixes: Vec<QuickFix>,
     /// Visual representation of the diagnostic as displayed in the CLI
     pub visual: String,
@@ -139,6 +201,42 @@ impl From<DiagnosticKind> for Kind {
     }
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Group {
+    /// Index of the capturing group. The first group has index 1.
+    pub index: u32,
+    /// Name of the group, absent if the group is unnamed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Location of the group in the pomsky expression
+    pub span: Span,
+}
+
+impl From<GroupInfo> for Group {
+    fn from(value: GroupInfo) -> Self {
+        Group {
+            index: value.index,
+            name: value.name,
+            span: value.span.range().unwrap_or_default().into(),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Flags {
+    /// Flags without which the regex is invalid or matches incorrectly, e.g.
+    /// "u" in JavaScript if the regex contains `\p{...}`
+    pub required: String,
+    /// Flags that are not required, but make the regex match as intended
+    pub recommended: String,
+}
+
+impl From<RegexFlags> for Flags {
+    fn from(value: RegexFlags) -> Self {
+        Flags { required: value.required, recommended: value.recommended }
+    }
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Timings {
     /// time of all performed compilation steps in microseconds
@@ -175,6 +273,35 @@ impl From<std::ops::Range<usize>> for Span {
     }
 }
 
+/// A range of source code as line and column numbers. Both are 1-based, and
+/// columns are counted in code points.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    /// The column after the last code point in the range
+    pub end_column: usize,
+}
+
+impl Region {
+    pub fn new(source_code: &str, range: std::ops::Range<usize>) -> Self {
+        let (start_line, start_column) = position(source_code, range.start);
+        let (end_line, end_column) = position(source_code, range.end);
+        Region { start_line, start_column, end_line, end_column }
+    }
+}
+
+/// Returns the line and column of a byte offset
+fn position(source_code: &str, offset: usize) -> (usize, usize) {
+    let before = source_code.get(..offset).unwrap_or(source_code);
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let line = before.matches('\n').count() + 1;
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct QuickFix {
     /// Short description what this quick fix does
@@ -201,6 +328,22 @@ pub struct Replacement {
     pub insert: String,
 }
 
+impl From<pomsky::diagnose::QuickFix> for QuickFix {
+    fn from(value: pomsky::diagnose::QuickFix) -> Self {
+        QuickFix {
+            description: value.description,
+            replacements: value.replacements.into_iter().map(Replacement::from).collect(),
+        }
+    }
+}
+
+impl From<pomsky::diagnose::Replacement> for Replacement {
+    fn from(value: pomsky::diagnose::Replacement) -> Self {
+        let range = value.span.range().unwrap_or_default();
+        Replacement { start: range.start, end: range.end, insert: value.insert }
+    }
+}
+
 impl Diagnostic {
     fn from(value: pomsky::diagnose::Diagnostic, source_code: Option<&str>) -> Self {
         let kind = value.kind.to_string();
@@ -220,7 +363,7 @@ impl Diagnostic {
             spans: value.span.range().into_iter().map(From::from).collect(),
             description: value.msg,
             help: value.help.into_iter().collect(),
-            fixes: vec![],
+            fixes: value.fixes.into_iter().map(QuickFix::from).collect(),
             visual,
         }
     }
@@ -0,0 +1,213 @@
+//! Output in the [SARIF 2.1.0] format, which is supported by code scanning
+//! tools
+//!
+//! [SARIF 2.1.0]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use pomsky::diagnose::DiagnosticCode;
+use serde::Serialize;
+
+use super::{CompilationResult, Diagnostic, Region, Severity};
+
+/// The diagnostics of one or more files, which are printed as a single SARIF
+/// log with one run
+#[derive(Debug, Default)]
+pub struct SarifLog {
+    rules: Vec<DiagnosticCode>,
+    results: Vec<SarifResult>,
+}
+
+impl SarifLog {
+    /// Adds the diagnostics of a compilation result. `path` is the file that
+    /// was compiled, or `None` if the expression didn't come from a file.
+    pub fn add(
+        &mut self,
+        path: Option<&str>,
+        result: &CompilationResult,
+        source_code: Option<&str>,
+    ) {
+        for diagnostic in &result.diagnostics {
+            let result = self.result(path, diagnostic, source_code);
+            self.results.push(result);
+        }
+    }
+
+    fn result(
+        &mut self,
+        path: Option<&str>,
+        diagnostic: &Diagnostic,
+        source_code: Option<&str>,
+    ) -> SarifResult {
+        let rule_index = diagnostic.code.map(|code| {
+            self.rules.iter().position(|&c| c == code).unwrap_or_else(|| {
+                self.rules.push(code);
+                self.rules.len() - 1
+            })
+        });
+
+        let mut text = diagnostic.description.clone();
+        for help in &diagnostic.help {
+            text.push_str("\nhelp: ");
+            text.push_str(help);
+        }
+
+        let locations = match path {
+            Some(path) => vec![Location {
+                physical_location: PhysicalLocation {
+                    artifact_location: ArtifactLocation { uri: uri(path) },
+                    region: match (source_code, diagnostic.spans.first()) {
+                        (Some(source_code), Some(span)) => {
+                            Some(Region::new(source_code, span.start..span.end))
+                        }
+                        _ => None,
+                    },
+                },
+            }],
+            None => vec![],
+        };
+
+        SarifResult {
+            rule_id: diagnostic.code.map(|code| code.to_string()),
+            rule_index,
+            level: match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            },
+            message: Message { text },
+            locations,
+        }
+    }
+
+    pub fn output(&self) {
+        let rules = self.rules.iter().map(|&code| Rule::new(code)).collect();
+        let log = Log {
+            schema: "https://json.schemastore.org/sarif-2.1.0.json",
+            version: "2.1.0",
+            runs: [Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: "pomsky",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: "https://pomsky-lang.org/",
+                        rules,
+                    },
+                },
+                column_kind: "unicodeCodePoints",
+                results: &self.results,
+            }],
+        };
+
+        match serde_json::to_string(&log) {
+            Ok(string) => println!("{string}"),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+}
+
+/// Converts a path to a relative URI reference
+fn uri(path: &str) -> String {
+    let mut uri = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            '\\' => uri.push('/'),
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' | '_' | '~' | '/' => uri.push(c),
+            _ => {
+                let mut buf = [0; 4];
+                for byte in c.encode_utf8(&mut buf).bytes() {
+                    uri.push_str(&format!("%{byte:02X}"));
+                }
+            }
+        }
+    }
+    uri
+}
+
+#[derive(Serialize)]
+struct Log<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: [Run<'a>; 1],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run<'a> {
+    tool: Tool,
+    column_kind: &'static str,
+    results: &'a [SarifResult],
+}
+
+#[derive(Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+/// Describes a diagnostic code
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: String,
+    name: String,
+    short_description: Message,
+    help: Message,
+}
+
+impl Rule {
+    fn new(code: DiagnosticCode) -> Self {
+        let explanation = code.explanation();
+        // the first paragraph of the explanation describes the diagnostic
+        let summary = explanation.split("\n\n").next().unwrap_or_default().replace('\n', " ");
+        Rule {
+            id: code.to_string(),
+            name: format!("{code:?}"),
+            short_description: Message { text: summary },
+            help: Message { text: explanation.to_string() },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    level: &'static str,
+    message: Message,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<Location>,
+}
+
+#[derive(Debug, Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+#[derive(Debug, Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
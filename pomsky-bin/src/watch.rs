@@ -0,0 +1,70 @@
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::args::{Args, Input};
+
+/// How often the watched files are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Compiles all files matched by the given paths, then polls them for changes
+/// and recompiles every file that was modified or added. This runs until the
+/// process is terminated.
+///
+/// Polling is used instead of file system events, so it works everywhere
+/// without OS-specific dependencies.
+pub(crate) fn watch(paths: &[OsString], args: &Args) -> ! {
+    let mut last_modified = HashMap::<PathBuf, SystemTime>::new();
+    let mut is_first_run = true;
+    // the files of the last `--out-dir` collision, which is reported only once
+    let mut colliding_files = None;
+
+    loop {
+        // glob patterns are expanded again every time, so new files are found
+        let files = Input::expand_paths(paths, true).unwrap_or_default();
+
+        let mut changed = Vec::new();
+        let mut modified = HashMap::with_capacity(files.len());
+        for file in &files {
+            // files that can't be accessed are skipped until they reappear
+            if let Ok(time) = std::fs::metadata(file).and_then(|m| m.modified()) {
+                if last_modified.get(file) != Some(&time) {
+                    changed.push(file.clone());
+                }
+                modified.insert(file.clone(), time);
+            }
+        }
+
+        let collision = match &args.out_dir {
+            Some(out_dir) if !changed.is_empty() => crate::check_out_files(out_dir, &files).err(),
+            _ => None,
+        };
+        if let Some(error) = collision {
+            if colliding_files.as_ref() != Some(&files) {
+                crate::print_error(error);
+            }
+            if is_first_run {
+                std::process::exit(crate::Outcome::Failure.exit_code());
+            }
+            // the changed files weren't compiled, so they are compiled once the collision is fixed
+            modified.retain(|file, _| !changed.contains(file));
+            colliding_files = Some(files);
+        } else {
+            colliding_files = None;
+            if !changed.is_empty() {
+                crate::compile_each(&changed, args);
+            }
+        }
+        last_modified = modified;
+        if is_first_run {
+            is_first_run = false;
+            efprintln!(C!"note" ": watching for changes, press Ctrl+C to stop");
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
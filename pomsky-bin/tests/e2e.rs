@@ -2,11 +2,14 @@
 #![cfg(not(target_os = "windows"))]
 
 use assert_cmd::prelude::*;
-use assert_fs::prelude::{FileWriteBin, FileWriteStr};
+use assert_fs::prelude::{FileWriteBin, FileWriteStr, PathAssert, PathChild};
 use predicates::reflection::{Case, Parameter, PredicateReflection};
 
 use pomsky::diagnose::DiagnosticCode;
-use pomsky_bin::{CompilationResult, Diagnostic, Kind, Severity, Span, Timings, Version};
+use pomsky_bin::{
+    CompilationResult, Diagnostic, Flags, Group, Kind, QuickFix, Replacement, Severity, Span,
+    Timings, Version,
+};
 
 use std::{fmt, process::Command};
 
@@ -122,17 +125,30 @@ USAGE:
     pomsky [OPTIONS] --path <PATH>
     command | pomsky [OPTIONS]
 
+COMMANDS:
+    explain  Explain a diagnostic code
+    gen      Generate source files with a constant for each pomsky file
+    repl     Start an interactive session
+
 ARGS:
     <INPUT>  Pomsky expression to compile
 
 OPTIONS:
         --allowed-features <FEATURE>...  Comma-separated list of allowed features [default: all enabled]
+        --check-all-flavors              Compile for every flavor and report which ones are supported
+        --deny-warnings                  Treat warnings as errors
+        --emit <KIND>                    Print the `regex` or a string `literal` [default: `regex`]
+        --fix <PATH>...                  Apply automatic fixes to files in place
     -f, --flavor <FLAVOR>                Regex flavor [default: `pcre`]
     -h, --help                           Print help information
+        --no-config                      Don't read settings from `pomsky.toml`
     -n, --no-new-line                    Don't print a new-line after the output
+    -o, --out-dir <DIR>                  Write the compiled regexes to this directory
+        --output-format <FORMAT>         Print the results as `human`, `json`, `sarif` or `gnu` [default: `human`]
     -p, --path <FILE>                    File containing the pomsky expression to compile
     -V, --version                        Print version information
     -W, --warnings <DIAGNOSTICS>         Disable certain warnings (disable all with `-W0`)
+    -w, --watch <PATH>...                Recompile files whenever they change
 "#, env!("CARGO_PKG_VERSION")));
 }
 
@@ -233,19 +249,87 @@ fn arg_input_with_flavor() {
     cmd.assert().success().stdout("(?<foo>test)+\n").stderr("");
 }
 
+#[test]
+fn flavor_with_version() {
+    let mut cmd = command(&["atomic('test')", "-f", "python@3.11"]);
+    cmd.assert().success().stdout("(?>test)\n").stderr("");
+
+    let mut cmd = command(&["<< 'test'", "-f", "js@es2018"]);
+    cmd.assert().success().stdout("(?<=test)\n").stderr("");
+
+    let mut cmd = command(&["[Greek]", "-f", "js@es2015"]);
+    cmd.assert().failure().stderr(predicates::str::contains("Unsupported feature"));
+
+    let mut cmd = command(&["'test'", "-f", "rust@1.0"]);
+    cmd.assert().failure().code(2).stderr(predicates::str::contains(
+        "a version can only be specified for 'javascript' and 'python'",
+    ));
+}
+
+#[test]
+fn check_all_flavors() {
+    let mut cmd = command(&["'test' (<< 'a')", "--check-all-flavors"]);
+    cmd.assert().failure().code(1).stderr("").stdout(
+        "FLAVOR        RESULT
+pcre          ok
+python        ok
+python-regex  ok
+java          ok
+javascript    ok (1 warning)
+dotnet        ok
+ruby          ok
+rust          unsupported: lookahead/behind
+re2           unsupported: lookahead/behind
+ere           unsupported: lookahead/behind
+icu           ok
+vim           ok
+emacs         unsupported: lookahead/behind
+",
+    );
+
+    let mut cmd = command(&["'test' (<< 'a')", "--check-all-flavors", "-Wcompat=0"]);
+    cmd.assert()
+        .failure()
+        .code(1)
+        .stderr("")
+        .stdout(predicates::str::contains("javascript    ok\n"));
+
+    let mut cmd = command(&["'test'", "--check-all-flavors"]);
+    cmd.assert().success().stderr("").stdout(predicates::str::contains("emacs         ok\n"));
+}
+
+#[test]
+fn list_features() {
+    let mut cmd = command(&["--list-features", "js"]);
+    cmd.assert()
+        .success()
+        .stderr("")
+        .stdout(predicates::str::contains("\natomic groups                                 no\n"))
+        .stdout(predicates::str::contains("\nforward reference                             no\n"))
+        .stdout(predicates::str::contains(
+            "\nlookbehind                                    warning\n",
+        ));
+
+    let mut cmd = command(&["--list-features", "js@es2018"]);
+    cmd.assert()
+        .success()
+        .stderr("")
+        .stdout(predicates::str::contains("\nlookbehind                                    yes\n"));
+}
+
 #[test]
 fn invalid_flavor() {
     let mut cmd = command(&[":foo('test')+", "-f", "jsx"]);
     cmd.assert().failure().stderr(format!(
         "{ERROR}'jsx' isn't a valid flavor
-  │ possible values: pcre, python, java, javascript, dotnet, ruby, rust
+  │ possible values: pcre, python, python-regex, java, javascript, dotnet, ruby, rust, re2, ere, icu, vim, emacs
 {USAGE}"
     ));
 
     let mut cmd = command_color(&[":foo('test')+", "-f", "jsx"]);
     cmd.assert().failure().stderr(format!(
         "{ERROR_COLOR}'jsx' isn't a valid flavor
-  {RED}│{RESET} possible values: pcre, python, java, javascript, dotnet, ruby, rust
+  {RED}│{RESET} possible values: pcre, python, python-regex, java, javascript, dotnet, ruby, rust, re2, ere, icu, vim, emacs
 {USAGE_COLOR}"
     ));
 }
@@ -294,6 +378,296 @@ fn path() {
         .stderr(format!("{ERROR}stream did not contain valid UTF-8\n"));
 }
 
+#[test]
+fn multiple_paths() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child("a.pom").write_str(":foo('test')+").unwrap();
+    dir.child("b.pom").write_str("'a' (<< 'b')").unwrap();
+    dir.child("c.txt").write_str("'c'").unwrap();
+    let glob = dir.path().join("*.pom");
+    let glob = glob.to_str().unwrap();
+    let [a, b, c] = ["a.pom", "b.pom", "c.txt"].map(|file| dir.path().join(file));
+    let [a, b, c] = [&a, &b, &c].map(|path| path.to_str().unwrap());
+
+    let mut cmd = command(&["-fJS", "-W0", "-p", glob]);
+    cmd.assert().success().stderr("").stdout(format!("{a}: (?<foo>test)+\n{b}: a(?<=b)\n"));
+
+    let mut cmd = command(&["-fRust", "-p", a, "-p", c]);
+    cmd.assert().success().stderr("").stdout(format!("{a}: (?P<foo>test)+\n{c}: c\n"));
+
+    let mut cmd = command(&["-fRust", "--json", "-p", glob]);
+    let output = cmd.assert().failure().code(1).stderr("").get_output().stdout.clone();
+    let results: std::collections::BTreeMap<String, CompilationResult> =
+        serde_json::from_slice(&output).unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(results[a].success);
+    assert_eq!(results[a].output.as_deref(), Some("(?P<foo>test)+"));
+    assert!(!results[b].success);
+    assert_eq!(results[b].diagnostics.len(), 1);
+
+    let pattern = dir.path().join("*.foo");
+    let pattern = pattern.to_str().unwrap();
+    let mut cmd = command(&["-p", pattern]);
+    cmd.assert()
+        .failure()
+        .code(2)
+        .stderr(predicates::str::contains(format!("No files match the pattern '{pattern}'")));
+}
+
+#[test]
+fn positional_paths() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child("patterns/a.pom").write_str(":foo('test')+").unwrap();
+    dir.child("patterns/b.pom").write_str("'b'").unwrap();
+    dir.child("f[1].pom").write_str("'f'").unwrap();
+
+    // the glob pattern is expanded by the shell
+    let pomsky = assert_cmd::cargo::cargo_bin("pomsky");
+    let mut cmd = Command::new("sh");
+    cmd.current_dir(dir.path()).arg("-c").arg(format!("{} -f js patterns/*.pom", pomsky.display()));
+    cmd.assert().success().stderr("").stdout("patterns/a.pom: (?<foo>test)+\npatterns/b.pom: b\n");
+
+    let mut cmd = command(&["-f", "js", "patterns"]);
+    cmd.current_dir(dir.path())
+        .assert()
+        .success()
+        .stderr("")
+        .stdout("patterns/a.pom: (?<foo>test)+\npatterns/b.pom: b\n");
+
+    let mut cmd = command(&["patterns/b.pom"]);
+    cmd.current_dir(dir.path()).assert().success().stderr("").stdout("b\n");
+
+    // existing files aren't glob patterns, even if they contain `[`
+    let mut cmd = command(&["f[1].pom"]);
+    cmd.current_dir(dir.path()).assert().success().stderr("").stdout("f\n");
+
+    let mut cmd = command(&["-p", "f[1].pom"]);
+    cmd.current_dir(dir.path()).assert().success().stderr("").stdout("f\n");
+
+    let mut cmd = command(&["-p", "patterns/b.pom", "rust"]);
+    cmd.current_dir(dir.path())
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicates::str::contains("You can only provide an input or a path, but not both"));
+}
+
+#[test]
+fn out_dir() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child("patterns/a.pom").write_str(":foo('test')+").unwrap();
+    dir.child("patterns/nested/b.c.pom").write_str("'b'").unwrap();
+    let patterns = dir.path().join("patterns");
+    let patterns = patterns.to_str().unwrap();
+    let out = dir.path().join("out");
+    let out = out.to_str().unwrap();
+
+    let mut cmd = command(&["-fJS", "-p", patterns, "-o", out]);
+    cmd.assert()
+        .success()
+        .stderr("")
+        .stdout(predicates::str::contains(format!("a.pom -> {out}/a.regex\n")))
+        .stdout(predicates::str::contains(format!("b.c.pom -> {out}/b.c.regex\n")));
+    dir.child("out/a.regex").assert("(?<foo>test)+");
+    dir.child("out/b.c.regex").assert("b");
+
+    dir.child("patterns/nested/a.pom").write_str("'a'").unwrap();
+    let mut cmd = command(&["-fJS", "-p", patterns, "-o", out]);
+    cmd.assert().failure().code(1).stderr(predicates::str::contains("would both be written to"));
+    dir.child("out/a.regex").assert("(?<foo>test)+");
+
+    let mut cmd = command(&["'a'", "-o", out]);
+    cmd.assert().failure().code(2).stderr(predicates::str::contains(
+        "'--out-dir' can only be used with '--path' or '--watch'",
+    ));
+
+    let mut cmd = command(&["--watch", "-fJS"]);
+    cmd.assert().failure().code(2).stderr(predicates::str::contains(
+        "'--watch' requires at least one file, directory or glob pattern",
+    ));
+}
+
+#[test]
+fn config_file() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child("pomsky.toml")
+        .write_str(
+            r#"
+flavor = "js"
+max-range-size = 2
+
+[[override]]
+files = "legacy/*.pom"
+flavor = "python"
+"#,
+        )
+        .unwrap();
+    dir.child("a.pom").write_str(":foo('a')").unwrap();
+    dir.child("legacy/b.pom").write_str(":foo('b')").unwrap();
+    dir.child("legacy/nested/c.pom").write_str(":foo('c')").unwrap();
+
+    let mut cmd = command(&["a.pom", "legacy"]);
+    cmd.current_dir(dir.path())
+        .assert()
+        .success()
+        .stderr("")
+        .stdout("a.pom: (?<foo>a)\nlegacy/b.pom: (?P<foo>b)\nlegacy/nested/c.pom: (?<foo>c)\n");
+
+    let mut cmd = command(&["-p", "legacy/b.pom", "-fJava"]);
+    cmd.current_dir(dir.path()).assert().success().stdout("(?<foo>b)\n");
+
+    let mut cmd = command(&["range '0'-'999'"]);
+    cmd.current_dir(dir.path())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Range is too big"));
+
+    let mut cmd = command(&["range '0'-'999'", "--no-config"]);
+    cmd.current_dir(dir.path()).assert().success();
+
+    dir.child("pomsky.toml").write_str("flavor = \"foo\"").unwrap();
+    let mut cmd = command(&["'a'"]);
+    cmd.current_dir(dir.path())
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicates::str::contains("pomsky.toml: 'foo' isn't a valid flavor"));
+}
+
+#[test]
+fn repl() {
+    let mut cmd = assert_cmd::Command::cargo_bin("pomsky").unwrap();
+    cmd.args(["repl", "-fJS"]).write_stdin(
+        "let x = 'b';
+:x('a')? x
+:test \"ab b\"
+:flavor rust
+let x = 'c';
+x
+:test c
+:test d
+:flavor jsx
+:q
+'unreachable'
+",
+    );
+    cmd.assert().success().stderr(predicates::str::contains("'jsx' isn't a valid flavor")).stdout(
+        r#"(?<x>a)?b
+match 1 at 0..2: "ab"
+  1 (x): "a"
+match 2 at 3..4: "b"
+  1 (x): no match
+(?P<x>a)?b
+c
+match 1 at 0..1: "c"
+no match
+"#,
+    );
+
+    let mut cmd = command(&["repl", "'a'"]);
+    cmd.assert().failure().code(2).stderr(predicates::str::contains(
+        "'repl' can't be used with an input, '--path' or '--watch'",
+    ));
+}
+
+#[test]
+fn emit_literal() {
+    let input = r#"'"/' [digit]"#;
+    let literal = |flavor, expected: &str| {
+        let mut cmd = command(&[input, "--emit=literal", "-f", flavor]);
+        cmd.assert().success().stderr("").stdout(format!("{expected}\n"));
+    };
+
+    literal("java", r#""\"/\\d""#);
+    literal("js", r#"/"\/\p{Nd}/u"#);
+    literal("python", r#"r'"/\d'"#);
+    literal("dotnet", r#"@"""/\d""#);
+    literal("ruby", r#"%r{"/\d}"#);
+    literal("pcre", r#"'/"\/\d/u'"#);
+    literal("rust", r##"r#""/\d"#"##);
+
+    let mut cmd = command(&[input, "--emit", "code"]);
+    cmd.assert().failure().code(2).stderr(predicates::str::contains(
+        "'code' isn't a valid value for '--emit', expected 'regex' or 'literal'",
+    ));
+}
+
+#[test]
+fn generate() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child("patterns/email-address.pom").write_str(":user(['a'-'z']+) '@' :host(.+)").unwrap();
+    dir.child("patterns/digits.pom").write_str("[digit]+").unwrap();
+    let patterns = dir.path().join("patterns/*.pom");
+    let patterns = patterns.to_str().unwrap();
+    let out = dir.path().join("out");
+    let out = out.to_str().unwrap();
+    let version = env!("CARGO_PKG_VERSION");
+
+    let mut cmd = command(&["gen", patterns, "--lang", "rust"]);
+    cmd.assert().success().stderr("").stdout(format!(
+        r#"// Generated by pomsky {version}. Do not edit.
+
+/// Compiled from `{dir}/patterns/digits.pom`
+pub const DIGITS: &str = r"\d+";
+
+/// Compiled from `{dir}/patterns/email-address.pom`
+pub const EMAIL_ADDRESS: &str = r"(?P<user>[a-z]+)@(?P<host>.+)";
+/// Index of the capturing group `user` in [`EMAIL_ADDRESS`]
+pub const EMAIL_ADDRESS_USER: usize = 1;
+/// Index of the capturing group `host` in [`EMAIL_ADDRESS`]
+pub const EMAIL_ADDRESS_HOST: usize = 2;
+"#,
+        dir = dir.path().display(),
+    ));
+
+    let mut cmd = command(&["gen", patterns, "--lang=ts,java", "-o", out]);
+    cmd.assert().success().stderr("").stdout(format!("{out}/patterns.ts\n{out}/Patterns.java\n"));
+    dir.child("out/patterns.ts").assert(predicates::str::contains(
+        "export const EMAIL_ADDRESS = /(?<user>[a-z]+)@(?<host>.+)/u;\n\
+        /** Index of the capturing group `user` in {@link EMAIL_ADDRESS} */\n\
+        export const EMAIL_ADDRESS_USER = 1;\n",
+    ));
+    dir.child("out/Patterns.java").assert(predicates::str::contains(
+        "    public static final Pattern DIGITS = \
+        Pattern.compile(\"\\\\d+\", Pattern.UNICODE_CHARACTER_CLASS);\n",
+    ));
+
+    let mut cmd = command(&["gen", patterns, "--lang=ts,java"]);
+    cmd.assert().failure().code(2).stderr(predicates::str::contains(
+        "'gen' requires '--out-dir' when generating code for multiple languages",
+    ));
+
+    dir.child("groups/email.pom").write_str(":domain(.+)").unwrap();
+    dir.child("groups/email_domain.pom").write_str(".+").unwrap();
+    let groups = dir.path().join("groups/*.pom");
+    let mut cmd = command(&["gen", groups.to_str().unwrap(), "--lang", "rust"]);
+    cmd.assert()
+        .failure()
+        .code(1)
+        .stdout("")
+        .stderr(predicates::str::contains("group 'domain' in"))
+        .stderr(predicates::str::contains("the constant 'EMAIL_DOMAIN'"));
+
+    let file = dir.child("case.pom");
+    file.write_str(":fooBar('a') :fooBAR('b')").unwrap();
+    let mut cmd = command(&["gen", file.path().to_str().unwrap(), "--lang", "rust"]);
+    cmd.assert()
+        .failure()
+        .code(1)
+        .stdout("")
+        .stderr(predicates::str::contains("group 'fooBar' in"))
+        .stderr(predicates::str::contains("group 'fooBAR' in"))
+        .stderr(predicates::str::contains("the constant 'CASE_FOO_BAR'"));
+
+    // `*/` in the path must not end the doc comment
+    let file = dir.child("comment*/end.pom");
+    file.write_str("'a'").unwrap();
+    let mut cmd = command(&["gen", file.path().to_str().unwrap(), "--lang=ts,java", "-o", out]);
+    cmd.assert().success().stderr("");
+    dir.child("out/patterns.ts").assert(predicates::str::contains("comment*&#47;end.pom` */\n"));
+    dir.child("out/Patterns.java").assert(predicates::str::contains("comment*&#47;end.pom} */\n"));
+}
+
 #[test]
 fn no_newline() {
     let mut cmd = command(&[":foo('test')+", "--no-new-line"]);
@@ -335,6 +709,159 @@ fn disable_warnings() {
     );
 }
 
+#[test]
+fn deny_warnings() {
+    let mut cmd = command(&["<< 'test'", "-WP0400=deny", "-fJS"]);
+    cmd.assert()
+        .failure()
+        .code(4)
+        .stdout("")
+        .stderr(predicates::str::contains("error P0400(compat)"))
+        .stderr(predicates::str::contains("due to denied warnings"));
+
+    let mut cmd = command(&["<< 'test'", "--deny-warnings", "-Wcompat=warn", "-fJS"]);
+    cmd.assert().success().stdout("(?<=test)\n");
+
+    let mut cmd = command(&["<< 'test'", "--deny-warnings", "-WP0400=0", "-fJS"]);
+    cmd.assert().success().stdout("(?<=test)\n").stderr("");
+
+    let mut cmd = command(&["<< 'test'", "-Wcompat=deny", "-fJS", "--json"]);
+    cmd.assert()
+        .failure()
+        .code(4)
+        .stdout(predicates::str::contains(r#""success":false"#))
+        .stdout(predicates::str::contains(r#""severity":"error""#));
+
+    let mut cmd = command(&["'test'", "-WP0301=deny"]);
+    cmd.assert().failure().code(2).stderr(predicates::str::contains(
+        "`P0301` diagnostics are errors and can't be configured",
+    ));
+
+    let mut cmd = command(&["'test'", "-Wcompat=error"]);
+    cmd.assert()
+        .failure()
+        .code(2)
+        .stderr(predicates::str::contains("'error' isn't a valid warning level"));
+}
+
+#[test]
+fn explain() {
+    let mut cmd = command(&["explain", "P0308"]);
+    cmd.assert()
+        .success()
+        .stderr("")
+        .stdout(predicates::str::starts_with(
+            "P0308\n\nA `let` statement contains a capturing group.",
+        ))
+        .stdout(predicates::str::contains("\n    let x = :('a');\n"));
+
+    let mut cmd = command(&["--explain", "p308"]);
+    cmd.assert().success().stdout(predicates::str::starts_with("P0308\n"));
+
+    let mut cmd = command(&["explain", "P9999"]);
+    cmd.assert()
+        .failure()
+        .code(2)
+        .stderr(predicates::str::contains("'P9999' isn't a valid diagnostic code"));
+}
+
+#[test]
+fn gnu_output() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child("a.pom").write_str("'a'\n  ä \\w").unwrap();
+    let a = dir.path().join("a.pom");
+    let a = a.to_str().unwrap();
+
+    let mut cmd = command(&["--output-format=gnu", "-p", a]);
+    cmd.assert().failure().code(1).stdout("").stderr(predicates::str::starts_with(format!(
+        "{a}:2:5: error: Backslash escapes are not supported [P0003]\n\
+        {a}:2:5: note: Replace `\\w` with `[w]`\n"
+    )));
+
+    let mut cmd = command(&["--output-format=gnu", "-fJS", "<< 'a'"]);
+    cmd.assert().success().stdout("(?<=a)\n").stderr(
+        "<input>:1:1: warning: Lookbehind is not supported in all browsers, e.g. Safari [P0400]\n\
+        <input>:1:1: note: Avoid lookbehind if the regex should work in different browsers\n",
+    );
+}
+
+#[test]
+fn sarif_output() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child("a.pom").write_str("'a'\n  ä \\w").unwrap();
+    dir.child("b c.pom").write_str("<< 'b'").unwrap();
+    let glob = dir.path().join("*.pom");
+    let glob = glob.to_str().unwrap();
+
+    let mut cmd = command(&["--output-format=sarif", "-fJS", "-p", glob]);
+    let output = cmd.assert().failure().code(1).stderr("").get_output().stdout.clone();
+    let log: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "pomsky");
+    assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "P0003");
+    assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "P0400");
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["ruleId"], "P0003");
+    assert_eq!(results[0]["level"], "error");
+    let location = &results[0]["locations"][0]["physicalLocation"];
+    assert!(location["artifactLocation"]["uri"].as_str().unwrap().ends_with("/a.pom"));
+    assert_eq!(
+        location["region"],
+        serde_json::json!({ "startLine": 2, "startColumn": 5, "endLine": 2, "endColumn": 7 })
+    );
+    assert_eq!(results[1]["ruleId"], "P0400");
+    assert_eq!(results[1]["ruleIndex"], 1);
+    assert_eq!(results[1]["level"], "warning");
+    let location = &results[1]["locations"][0]["physicalLocation"];
+    assert!(location["artifactLocation"]["uri"].as_str().unwrap().ends_with("/b%20c.pom"));
+
+    let mut cmd = command(&["--output-format=sarif", "--json", "x"]);
+    cmd.assert()
+        .failure()
+        .code(2)
+        .stderr(predicates::str::contains("'--json' can't be used with '--output-format'"));
+}
+
+#[test]
+fn fix() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let file = dir.child("a.pom");
+    file.write_str("<% (?:'a' | \\d)+ (?<name>[.])\n\\p{Greek} \\1").unwrap();
+    let path = file.path().to_str().unwrap();
+
+    let mut cmd = command(&["--fix", path]);
+    cmd.assert()
+        .failure()
+        .code(1)
+        .stdout(format!("{path}: applied 5 fixes\n"))
+        .stderr(predicates::str::contains("If this is a backreference"));
+    file.assert("^ ('a' | [d])+ :name([.])\n[Greek] \\1");
+
+    // `[.]` is only reported once the lexer errors are fixed, so it's fixed in a second round
+    file.write_str("<% (?:'a' | \\d)+ (?<name>[.])").unwrap();
+    let mut cmd = command(&["--fix", path]);
+    cmd.assert().success().stderr("").stdout(format!("{path}: applied 5 fixes\n"));
+    file.assert("^ ('a' | [d])+ :name(.)");
+
+    let mut cmd = command(&["--fix", path]);
+    cmd.assert().success().stderr("").stdout("");
+
+    file.write_str(":('a') \\g1 \\k<-1>").unwrap();
+    let mut cmd = command(&["--fix", path]);
+    cmd.assert().success().stderr("").stdout(format!("{path}: applied 2 fixes\n"));
+    file.assert(":('a') ::1 ::-1");
+
+    let mut cmd = command(&["--fix"]);
+    cmd.assert()
+        .failure()
+        .code(2)
+        .stderr(predicates::str::contains("'--fix' requires at least one file"));
+}
+
 #[test]
 fn wrong_order() {
     let mut cmd = command(&["-pf", "file.txt", "rust"]);
@@ -375,6 +902,50 @@ fn json_output() {
             success: true,
             output: Some("..\\w".into()),
             diagnostics: vec![],
+            groups: vec![],
+            flags: Flags { required: "".into(), recommended: "u".into() },
+            timings: Timings { all: 0 },
+        }))
+        .stderr("");
+}
+
+#[test]
+fn json_output_groups() {
+    let mut cmd = command(&[":('a') :name('b' :('c'))", "--json"]);
+    cmd.assert()
+        .success()
+        .stdout(Output::new(CompilationResult {
+            version: Version::V1,
+            success: true,
+            output: Some("(a)(?P<name>b(c))".into()),
+            diagnostics: vec![],
+            groups: vec![
+                Group { index: 1, name: None, span: Span { start: 0, end: 6, label: None } },
+                Group {
+                    index: 2,
+                    name: Some("name".into()),
+                    span: Span { start: 7, end: 24, label: None },
+                },
+                Group { index: 3, name: None, span: Span { start: 17, end: 23, label: None } },
+            ],
+            flags: Flags::default(),
+            timings: Timings { all: 0 },
+        }))
+        .stderr("");
+}
+
+#[test]
+fn json_output_flags() {
+    let mut cmd = command(&["[Greek] 'ä'", "--json", "-fJS"]);
+    cmd.assert()
+        .success()
+        .stdout(Output::new(CompilationResult {
+            version: Version::V1,
+            success: true,
+            output: Some("\\p{sc=Greek}ä".into()),
+            diagnostics: vec![],
+            groups: vec![],
+            flags: Flags { required: "u".into(), recommended: "".into() },
             timings: Timings { all: 0 },
         }))
         .stderr("");
@@ -415,6 +986,8 @@ fn json_output_warnings() {
                     visual: String::new(),
                 },
             ],
+            groups: vec![],
+            flags: Flags { required: "".into(), recommended: "u".into() },
             timings: Timings { all: 0 },
         }))
         .stderr("");
@@ -437,7 +1010,10 @@ fn json_output_errors() {
                     spans: vec![Span { start: 1, end: 2, label: None }],
                     description: "`[.]` is deprecated".into(),
                     help: vec!["Use `.` without brackets instead".into()],
-                    fixes: vec![],
+                    fixes: vec![QuickFix {
+                        description: "Replace `[.]` with `.`".into(),
+                        replacements: vec![Replacement { start: 0, end: 3, insert: ".".into() }],
+                    }],
                     visual: String::from(
                         "error P0105(deprecated): 
   × `[.]` is deprecated
@@ -450,6 +1026,8 @@ fn json_output_errors() {
 ",
                     ),
                 }],
+                groups: vec![],
+                flags: Flags::default(),
                 timings: Timings { all: 0 },
             })
             .ignore_visual(false),
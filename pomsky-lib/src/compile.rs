@@ -5,6 +5,7 @@ use pomsky_syntax::exprs::Rule;
 use crate::{
     diagnose::{CompileError, Diagnostic},
     exprs::repetition::RegexQuantifier,
+    output::GroupInfo,
     regex::Regex,
 };
 
@@ -17,6 +18,7 @@ pub(crate) struct CompileState<'c, 'i> {
     pub(crate) used_names: HashMap<String, u32>,
     pub(crate) groups_count: u32,
     pub(crate) has_named: bool,
+    pub(crate) groups: Vec<GroupInfo>,
 
     pub(crate) default_quantifier: RegexQuantifier,
     pub(crate) variables: Vec<(&'i str, &'c Rule<'i>)>,
@@ -47,6 +49,7 @@ impl<'c, 'i> CompileState<'c, 'i> {
             used_names,
             groups_count,
             has_named,
+            groups: vec![],
 
             default_quantifier,
             variables,
@@ -22,6 +22,15 @@ impl CompileError {
         }
     }
 
+    /// Returns the regex feature that caused this error, if the error is
+    /// that the feature is unsupported by the regex flavor
+    pub(crate) fn unsupported_feature(&self) -> Option<Feature> {
+        match self.kind {
+            CompileErrorKind::Unsupported(feature, _) => Some(feature),
+            _ => None,
+        }
+    }
+
     /// Create a [Diagnostic] from this error.
     #[must_use]
     pub fn diagnostic(&self, source_code: &str) -> Diagnostic {
@@ -78,6 +87,9 @@ pub(crate) enum CompileErrorKind {
     },
     RecursiveVariable,
     RangeIsTooBig(u8),
+    /// The flavor version belongs to the first flavor, but the second flavor is
+    /// targeted
+    FlavorVersionMismatch(RegexFlavor, RegexFlavor),
 }
 
 impl CompileErrorKind {
@@ -137,6 +149,12 @@ impl core::fmt::Display for CompileErrorKind {
             CompileErrorKind::RangeIsTooBig(digits) => {
                 write!(f, "Range is too big, it isn't allowed to contain more than {digits} digits")
             }
+            CompileErrorKind::FlavorVersionMismatch(version_flavor, flavor) => {
+                write!(
+                    f,
+                    "A `{version_flavor:?}` version can't be used with the `{flavor:?}` regex flavor"
+                )
+            }
         }
     }
 }
@@ -1,4 +1,7 @@
-use std::fmt::{self, Write};
+use std::{
+    fmt::{self, Write},
+    str::FromStr,
+};
 
 use pomsky_syntax::diagnose::{
     CharClassError, CharStringError, CodePointError, LexErrorMsg, ParseErrorKind, ParseWarningKind,
@@ -84,12 +87,34 @@ diagnostic_code! {
         RecursiveVariable = 311,
         RangeIsTooBig = 312,
         RecursionLimit = 313,
+        FlavorVersionMismatch = 314,
 
         // Warning indicating something might not be supported
         PossiblyUnsupported = 400,
     }
 }
 
+impl DiagnosticCode {
+    /// Returns a long-form description of the diagnostic, with an example and
+    /// suggestions how to fix it.
+    ///
+    /// The text is wrapped at 80 columns. Code examples are indented by four
+    /// spaces, and code within a sentence is enclosed in backticks.
+    pub fn explanation(self) -> &'static str {
+        super::explanation::explanation(self)
+    }
+}
+
+/// Parses a diagnostic code such as `P0308`. The `P` is optional.
+impl FromStr for DiagnosticCode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = s.strip_prefix(['P', 'p']).unwrap_or(s);
+        number.parse::<u16>().map_err(|_| ()).and_then(DiagnosticCode::try_from)
+    }
+}
+
 impl fmt::Display for DiagnosticCode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let _ = f.write_char('P');
@@ -194,6 +219,7 @@ impl<'a> From<&'a CompileErrorKind> for DiagnosticCode {
             C::UnknownVariable { .. } => Self::UnknownVariable,
             C::RecursiveVariable => Self::RecursiveVariable,
             C::RangeIsTooBig(_) => Self::RangeIsTooBig,
+            C::FlavorVersionMismatch(..) => Self::FlavorVersionMismatch,
             C::NegatedHorizVertSpace => Self::NegatedHorizVertSpace,
         }
     }
@@ -206,3 +232,16 @@ impl<'a> From<&'a ParseWarningKind> for DiagnosticCode {
         }
     }
 }
+
+#[test]
+fn explanations() {
+    for number in 0..1000 {
+        if let Ok(code) = DiagnosticCode::try_from(number) {
+            assert_eq!(code.to_string().parse(), Ok(code));
+            assert!(!code.explanation().is_empty());
+            assert!(code.explanation().lines().all(|line| line.chars().count() <= 80));
+        }
+    }
+    assert_eq!("p308".parse(), Ok(DiagnosticCode::CaptureInLet));
+    assert_eq!("P9999".parse::<DiagnosticCode>(), Err(()));
+}
@@ -43,6 +43,7 @@ impl From<&CompileErrorKind> for DiagnosticKind {
                 DiagnosticKind::Unsupported
             }
             K::RangeIsTooBig(_) => DiagnosticKind::Limits,
+            K::FlavorVersionMismatch(..) => DiagnosticKind::Other,
         }
     }
 }
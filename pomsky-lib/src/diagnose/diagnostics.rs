@@ -3,7 +3,9 @@ use pomsky_syntax::{
     Span,
 };
 
-use super::{diagnostic_code::DiagnosticCode, CompileError, CompileErrorKind, DiagnosticKind};
+use super::{
+    diagnostic_code::DiagnosticCode, CompileError, CompileErrorKind, DiagnosticKind, QuickFix,
+};
 
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -23,6 +25,8 @@ pub struct Diagnostic {
     pub span: Span,
     /// The kind or origin of error/warning
     pub kind: DiagnosticKind,
+    /// Fixes that can be applied automatically. Currently, there is at most one.
+    pub fixes: Vec<QuickFix>,
 }
 
 #[cfg(feature = "miette")]
@@ -61,6 +65,7 @@ impl Diagnostic {
     ) -> Self {
         let range = error_span.range().unwrap_or(0..source_code.len());
         let slice = &source_code[range.clone()];
+        let fixes = super::quick_fix::get_fix(kind, source_code, range.clone());
         let mut span = Span::from(range);
 
         let help = super::help::get_help(kind, slice, &mut span);
@@ -73,6 +78,7 @@ impl Diagnostic {
             help,
             span,
             kind: DiagnosticKind::from(kind),
+            fixes: fixes.into_iter().collect(),
         }
     }
 
@@ -95,6 +101,7 @@ impl Diagnostic {
                     help: Some(format!("Perhaps you meant `{similar}`")),
                     span: Span::from(range),
                     kind: DiagnosticKind::Resolve,
+                    fixes: vec![],
                 }
             }
             CompileErrorKind::EmptyClassNegated { group1, group2 } => {
@@ -111,6 +118,7 @@ impl Diagnostic {
                     )),
                     span: Span::from(range),
                     kind: DiagnosticKind::Resolve,
+                    fixes: vec![],
                 }
             }
             kind => {
@@ -125,6 +133,7 @@ impl Diagnostic {
                     help: None,
                     span,
                     kind: DiagnosticKind::from(kind),
+                    fixes: vec![],
                 }
             }
         }
@@ -141,6 +150,7 @@ impl Diagnostic {
             help: None,
             span,
             kind: DiagnosticKind::from(kind),
+            fixes: vec![],
         }
     }
 
@@ -160,7 +170,15 @@ impl Diagnostic {
         msg: String,
         help: Option<String>,
     ) -> Self {
-        Diagnostic { severity, code, msg, help, span: Span::empty(), kind: DiagnosticKind::Other }
+        Diagnostic {
+            severity,
+            code,
+            msg,
+            help,
+            span: Span::empty(),
+            kind: DiagnosticKind::Other,
+            fixes: vec![],
+        }
     }
 
     /// Returns a value that can display the diagnostic with the [`Display`]
@@ -0,0 +1,553 @@
+//! Long-form explanations of diagnostic codes, see [`DiagnosticCode::explanation`]
+
+use super::DiagnosticCode;
+
+pub(super) fn explanation(code: DiagnosticCode) -> &'static str {
+    use DiagnosticCode as C;
+
+    match code {
+        C::UnknownToken => {
+            "\
+The expression contains a character that isn't valid pomsky syntax.
+
+Erroneous example:
+
+    'a' § 'b'
+
+Characters that aren't part of the syntax must be put in a string, e.g. `'§'`,
+or written as a code point, e.g. `U+A7`."
+        }
+        C::RegexGroupSyntax => {
+            "\
+A group was written with regex syntax, e.g. `(?:...)` or `(?<name>...)`, which
+pomsky doesn't use.
+
+Erroneous example:
+
+    (?:'a' | 'b')+
+
+Groups are non-capturing by default. Capturing groups start with `:`, named
+capturing groups with `:name`, and lookarounds are written with `>>`, `<<`,
+`!>>` and `!<<`:
+
+    ('a' | 'b')+
+    :name('test')
+    >> 'foo'"
+        }
+        C::RegexBackslashSyntax => {
+            "\
+A backslash escape such as `\\d` or `\\n` was used outside of a string. Pomsky
+doesn't use backslashes for escaping.
+
+Erroneous example:
+
+    \\d+ \\n
+
+Use the equivalent character classes, or put the character in a string:
+
+    [digit]+ [n]
+    [d]+ U+0A
+
+The help message of the error usually contains the replacement."
+        }
+        C::UnclosedString => {
+            "\
+A string literal was started, but the closing quote is missing.
+
+Erroneous example:
+
+    'test
+
+Add the missing quote. Strings in double quotes can contain escaped quotes
+(`\\\"`), while strings in single quotes can't contain single quotes at all:
+
+    'test'
+    \"it's a \\\"test\\\"\""
+        }
+        C::DeprecatedToken => {
+            "\
+The `<%` and `%>` tokens were removed. They used to match the start and the end
+of the string.
+
+Erroneous example:
+
+    <% 'test' %>
+
+Use `^` and `$` instead:
+
+    ^ 'test' $"
+        }
+        C::UnexpectedToken => {
+            "\
+The parser found a token where it expected something else, for example a
+closing parenthesis, or an expression after `|`.
+
+Erroneous example:
+
+    'a' ('b' | 'c'
+
+The error message lists what was expected at this position:
+
+    'a' ('b' | 'c')"
+        }
+        C::UnexpectedReservedWord => {
+            "\
+A reserved word such as `let`, `lazy`, `greedy`, `range` or `atomic` was used
+where it isn't allowed, e.g. as a variable or group name.
+
+Erroneous example:
+
+    :let('a')
+
+Choose a different name:
+
+    :letter('a')"
+        }
+        C::NonAsciiIdentAfterColon => {
+            "\
+The name of a capturing group contains a character that isn't ASCII. Most regex
+engines only allow ASCII letters, digits and underscores in group names.
+
+Erroneous example:
+
+    :bär('a')
+
+Use only ASCII characters:
+
+    :baer('a')"
+        }
+        C::IdentTooLong => {
+            "\
+The name of a capturing group is longer than 32 code points, which isn't
+supported by all regex engines.
+
+Erroneous example:
+
+    :a_very_long_name_that_nobody_wants_to_type('a')
+
+Use a shorter name:
+
+    :short_name('a')"
+        }
+        C::RangeIsNotIncreasing => {
+            "\
+The first number of a `range` expression is greater than the second one.
+
+Erroneous example:
+
+    range '9'-'1'
+
+Swap the numbers:
+
+    range '1'-'9'"
+        }
+        C::DeprecatedSyntax => {
+            "\
+Deprecated syntax was used. Syntax that is still supported produces a warning,
+which can be disabled with `-W P0105=allow` in the CLI. Syntax that was removed,
+such as `[.]`, is an error.
+
+Erroneous example:
+
+    [.]
+
+Replace the syntax as described in the help message, e.g. with `.` without the
+brackets:
+
+    ."
+        }
+        C::UnallowedNot => {
+            "\
+The `!` operator was applied to an expression that can't be negated. Only
+character classes, code points, word boundaries and lookarounds can be negated.
+
+Erroneous example:
+
+    !'abc'
+
+Negate a character class or a lookaround instead:
+
+    !['abc']
+    !>> 'abc'"
+        }
+        C::UnallowedMultiNot => {
+            "\
+An expression was negated more than once, which is redundant.
+
+Erroneous example:
+
+    !!['a']
+
+Two negations cancel each other out, so remove both of them:
+
+    ['a']"
+        }
+        C::InvalidEscapeInString => {
+            "\
+A string in double quotes contains an escape sequence other than `\\\\` and
+`\\\"`.
+
+Erroneous example:
+
+    \"\\n\"
+
+Escape sequences for line breaks, tabs etc. aren't supported in strings. Use a
+character class or a code point instead:
+
+    [n]
+    U+0A"
+        }
+        C::CodePointInvalid => {
+            "\
+A code point is greater than `U+10FFFF`, or is a surrogate (`U+D800` to
+`U+DFFF`), which can't be matched.
+
+Erroneous example:
+
+    U+FFFFFFF
+
+Use a code point between `U+0` and `U+10FFFF`:
+
+    U+FFFFF"
+        }
+        C::InvalidNumber => {
+            "\
+A number couldn't be parsed, or it is too large, e.g. in a repetition or a
+reference.
+
+Erroneous example:
+
+    'a'{99999}
+
+Use a smaller number:
+
+    'a'{999}"
+        }
+        C::RepetitionNotAscending => {
+            "\
+The lower bound of a repetition is greater than the upper bound.
+
+Erroneous example:
+
+    'a'{3,2}
+
+Swap the numbers:
+
+    'a'{2,3}"
+        }
+        C::RepetitionChain => {
+            "\
+Two repetitions follow each other, which is ambiguous. This includes a `?`
+after a repetition, which makes it lazy in regexes, but not in pomsky.
+
+Erroneous example:
+
+    'a'{3}{2}
+    'a'+?
+
+Wrap the first repetition in parentheses, or use the `lazy` keyword:
+
+    ('a'{3}){2}
+    ('a'+)?
+    'a'+ lazy"
+        }
+        C::CharRangeStringEmpty => {
+            "\
+A character range contains an empty string.
+
+Erroneous example:
+
+    ['a'-'']
+
+Both ends of a range must contain exactly one code point:
+
+    ['a'-'z']"
+        }
+        C::CharRangeTooManyCodePoints => {
+            "\
+A string in a character range contains more than one code point.
+
+Erroneous example:
+
+    ['ab'-'c']
+
+Both ends of a range must contain exactly one code point. To also match other
+characters, add them to the character class:
+
+    ['a'-'c' 'ab']"
+        }
+        C::CharClassHasDescendingRange => {
+            "\
+The first character of a range in a character class is greater than the second
+one.
+
+Erroneous example:
+
+    ['z'-'a']
+
+Swap the characters:
+
+    ['a'-'z']"
+        }
+        C::CharClassUnknownShorthand => {
+            "\
+A character class contains a name that isn't a known shorthand, POSIX class,
+Unicode category, script or block.
+
+Erroneous example:
+
+    [foo]
+
+Check the spelling. The help message may suggest a similar name. Strings and
+code points must be quoted or written with `U+`:
+
+    [word]
+    ['foo']"
+        }
+        C::CharClassIllegalNegation => {
+            "\
+A character class item was negated which can't be negated, such as an ASCII
+class like `ascii` or `ascii_alpha`.
+
+Erroneous example:
+
+    [!ascii]
+
+Negate the whole character class instead:
+
+    ![ascii]"
+        }
+        C::CharClassUnallowedCombination => {
+            "\
+A character class contains a combination of items that isn't allowed. This
+code is currently not emitted."
+        }
+        C::NegatedHorizVertSpace => {
+            "\
+`horiz_space` and `vert_space` were negated within a character class, which
+isn't supported.
+
+Erroneous example:
+
+    [!horiz_space w]
+
+Negate the whole character class instead, or use a lookahead:
+
+    ![horiz_space]
+    (!>> [horiz_space]) [w]"
+        }
+        C::LetBindingExists => {
+            "\
+A variable was declared twice in the same scope.
+
+Erroneous example:
+
+    let x = 'a';
+    let x = 'b';
+    x
+
+Rename one of the variables, or declare it in a nested scope:
+
+    let x = 'a';
+    let y = 'b';
+    x y"
+        }
+        C::UnsupportedRegexFeature => {
+            "\
+The expression uses a regex feature that isn't supported by the selected regex
+flavor, e.g. `Grapheme` in JavaScript or lookbehind in Rust.
+
+Erroneous example, with the JavaScript flavor:
+
+    Grapheme
+
+Rewrite the expression without the feature, or select a different flavor. The
+CLI command `pomsky --list-features <FLAVOR>` shows which features a flavor
+supports, and `pomsky --check-all-flavors` shows which flavors support the
+expression. Some features can be emulated, e.g. `Grapheme` with
+`CompileOptions::emulate_grapheme`."
+        }
+        C::UnsupportedPomskySyntax => {
+            "\
+The expression uses a pomsky feature that was disabled with `allowed_features`
+in the compile options, or `--allowed-features` in the CLI.
+
+Erroneous example, with `--allowed-features=variables`:
+
+    :('test')
+
+Use only the allowed features, e.g. a non-capturing group:
+
+    ('test')"
+        }
+        C::HugeReference => {
+            "\
+A reference to a group number greater than 99 was used, which isn't supported
+by most regex engines.
+
+Erroneous example:
+
+    ::100
+
+Use a named group and reference it by name:
+
+    :name('test') ::name"
+        }
+        C::UnknownReference => {
+            "\
+A reference points to a group that doesn't exist, or a relative reference is 0.
+
+Erroneous example:
+
+    :('a') ::2
+
+Make sure the group exists. Groups are numbered from left to right, starting
+with 1. Relative references such as `::-1` count from the reference:
+
+    :('a') :('b') ::2
+    :('a') ::-1"
+        }
+        C::NameUsedMultipleTimes => {
+            "\
+Two capturing groups have the same name, which isn't allowed.
+
+Erroneous example:
+
+    :a('x') :a('y')
+
+Give each group a unique name:
+
+    :a('x') :b('y')"
+        }
+        C::EmptyClass => {
+            "\
+A character class doesn't contain anything, so it can't match anything.
+
+Erroneous example:
+
+    []
+
+Add characters to the class. To match nothing, use an empty negative lookahead:
+
+    ['a']
+    !>> ''"
+        }
+        C::EmptyClassNegated => {
+            "\
+A negated character class contains an item and its negation, so it matches
+every code point, and the negated class matches nothing.
+
+Erroneous example:
+
+    ![s !s]
+
+Remove the negation of the class, if it should match everything:
+
+    [s !s]"
+        }
+        C::CaptureInLet => {
+            "\
+A `let` statement contains a capturing group. This isn't supported yet, because
+using the variable more than once would produce multiple groups with the same
+number or name.
+
+Erroneous example:
+
+    let x = :('a');
+    x
+
+Put the capturing group around the variable:
+
+    let x = 'a';
+    :(x)"
+        }
+        C::ReferenceInLet => {
+            "\
+A `let` statement contains a reference. This isn't supported yet, because the
+group it points to may be different where the variable is used.
+
+Erroneous example:
+
+    let x = ::1;
+    :('a') x
+
+Put the reference where the variable is used:
+
+    :('a') ::1"
+        }
+        C::UnknownVariable => {
+            "\
+A variable was used that doesn't exist. Variables must be declared with `let`
+before the expression that uses them, or in an enclosing scope.
+
+Erroneous example:
+
+    y
+
+Declare the variable, or check the spelling. The help message may suggest a
+similar name:
+
+    let y = 'test';
+    y"
+        }
+        C::RecursiveVariable => {
+            "\
+A variable is used in its own declaration, possibly indirectly. Pomsky doesn't
+support recursion.
+
+Erroneous example:
+
+    let x = 'a' x?;
+    x
+
+Use a repetition instead:
+
+    let x = 'a'+;
+    x"
+        }
+        C::RangeIsTooBig => {
+            "\
+A `range` expression contains numbers with more digits than allowed by
+`max_range_size`, since the generated regex grows quickly with the number of
+digits. The CLI allows up to 12 digits by default.
+
+Erroneous example:
+
+    range '0'-'9999999999999'
+
+Use a smaller range, or increase `max_range_size` in the compile options or in
+`pomsky.toml`:
+
+    range '0'-'999999999999'"
+        }
+        C::RecursionLimit => {
+            "\
+The expression is nested too deeply, e.g. with hundreds of parentheses, so it
+can't be parsed without risking a stack overflow.
+
+Reduce the nesting, e.g. by using variables for parts of the expression."
+        }
+        C::FlavorVersionMismatch => {
+            "\
+The `flavor_version` in the compile options belongs to a different regex flavor
+than the targeted one, e.g. `FlavorVersion::JavaScript(EcmaVersion::Es2018)`
+with the `Python` flavor.
+
+Use a version of the targeted flavor, or no version at all. In the CLI, the
+version is appended to the flavor, e.g. `--flavor python@3.11`."
+        }
+        C::PossiblyUnsupported => {
+            "\
+The expression uses a feature that is supported by the selected regex flavor,
+but not by all engines that implement it. For example, lookbehind isn't
+supported in older versions of Safari. This is a warning, which can be disabled
+with `-W P0400=allow` in the CLI.
+
+Example, with the JavaScript flavor:
+
+    << 'test'
+
+Avoid the feature if the regex should work everywhere, or disable the warning."
+        }
+    }
+}
@@ -12,7 +12,7 @@ pub enum Feature {
     Grapheme,
     /// Unicode blocks, e.g. `\p{InBasic_Latin}`
     UnicodeBlock,
-    /// Unicode properties, e.g. `\p{Whitespace}`
+    /// Unicode properties, e.g. `\p{Letter}` or `\p{Greek}`
     UnicodeProp,
     /// Backreferences, e.g. `\4`
     Backreference,
@@ -41,10 +41,90 @@ pub enum Feature {
     MixedReferences,
     /// Lookarounds can't be repeated in Ruby, even when wrapped in a group
     RepeatedAssertion,
+    /// Free-spacing mode (the `x` flag), which is needed for
+    /// [`OutputStyle::Readable`](crate::options::OutputStyle::Readable)
+    FreeSpacing,
+    /// Lazy quantifiers, e.g. `a+?`
+    LazyQuantifiers,
+    /// Word boundaries, e.g. `\b`
+    WordBoundaries,
+    /// A negated shorthand in a character class, e.g. `[\Sa]`. This is not
+    /// supported in POSIX ERE, Vim and Emacs, where shorthands are emulated
+    /// with POSIX classes such as `[:space:]`, which can't be negated.
+    NegativeShorthandInClass,
+    /// Backreferences to a group with a number above 9, e.g. `\12`
+    BackreferenceAbove9,
+    /// Lookbehind containing a repetition without an upper bound, e.g.
+    /// `(?<=a+)`
+    UnboundedLookbehind,
+    /// Lookbehind, e.g. `(?<=lookbehind)`
+    Lookbehind,
+    /// Lazy repetition with bounds other than `*`, `+` and `?`, e.g. `a{2,5}?`
+    LazyBoundedRepetition,
+    /// Unicode properties other than general categories, scripts and blocks,
+    /// e.g. `\p{Alphabetic}`
+    UnicodeBinaryProp,
+}
+
+/// Whether a regex flavor supports a [`Feature`], as returned by
+/// [`RegexFlavor::supports`](crate::options::RegexFlavor::supports)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Support {
+    /// The feature is supported
+    Yes,
+    /// The feature is not supported, so using it is an error
+    No,
+    /// The feature is not supported natively, but pomsky can emulate it. Some
+    /// emulations must be enabled in the
+    /// [`CompileOptions`](crate::options::CompileOptions)
+    Emulated,
+    /// The feature is supported, but not by every regex engine of the flavor,
+    /// so pomsky emits a warning
+    Warn,
+}
+
+impl Support {
+    /// Returns a short, human-readable description
+    pub fn name(self) -> &'static str {
+        match self {
+            Support::Yes => "yes",
+            Support::No => "no",
+            Support::Emulated => "emulated",
+            Support::Warn => "warning",
+        }
+    }
 }
 
 impl Feature {
-    pub(super) fn name(self) -> &'static str {
+    /// All regex features
+    pub const ALL: &'static [Feature] = &[
+        Feature::NamedCaptureGroups,
+        Feature::AtomicGroups,
+        Feature::Lookaround,
+        Feature::Lookbehind,
+        Feature::UnboundedLookbehind,
+        Feature::Grapheme,
+        Feature::UnicodeBlock,
+        Feature::UnicodeProp,
+        Feature::UnicodeBinaryProp,
+        Feature::Backreference,
+        Feature::BackreferenceAbove9,
+        Feature::ForwardReference,
+        Feature::RelativeReference,
+        Feature::NonNegativeRelativeReference,
+        Feature::MixedReferences,
+        Feature::NegativeShorthandW,
+        Feature::NegativeShorthandInClass,
+        Feature::RepeatedAssertion,
+        Feature::FreeSpacing,
+        Feature::LazyQuantifiers,
+        Feature::LazyBoundedRepetition,
+        Feature::WordBoundaries,
+    ];
+
+    /// Returns a short, human-readable description of the feature
+    pub fn name(self) -> &'static str {
         match self {
             Feature::NamedCaptureGroups => "named capturing groups",
             Feature::AtomicGroups => "atomic groups",
@@ -59,6 +139,15 @@ impl Feature {
             Feature::NegativeShorthandW => "negative `\\w` shorthand in character class",
             Feature::MixedReferences => "references to both named and numbered groups",
             Feature::RepeatedAssertion => "single repeated assertion",
+            Feature::FreeSpacing => "free-spacing mode (x flag)",
+            Feature::LazyQuantifiers => "lazy quantifiers",
+            Feature::WordBoundaries => "word boundaries",
+            Feature::NegativeShorthandInClass => "negative shorthand in character class",
+            Feature::BackreferenceAbove9 => "backreference to a group above 9",
+            Feature::UnboundedLookbehind => "lookbehind with unbounded length",
+            Feature::LazyBoundedRepetition => "lazy repetition with bounds",
+            Feature::Lookbehind => "lookbehind",
+            Feature::UnicodeBinaryProp => "Unicode binary properties (\\p{Alphabetic})",
         }
     }
 }
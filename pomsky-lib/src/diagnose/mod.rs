@@ -6,12 +6,15 @@ pub(crate) use warning::{CompatWarning, CompileWarningKind};
 pub use diagnostic_code::DiagnosticCode;
 pub use diagnostic_kind::DiagnosticKind;
 pub use diagnostics::{Diagnostic, Severity};
-pub use feature::Feature;
+pub use feature::{Feature, Support};
+pub use quick_fix::{QuickFix, Replacement};
 
 mod compile_error;
 mod diagnostic_code;
 mod diagnostic_kind;
 mod diagnostics;
+mod explanation;
 mod feature;
 mod help;
+mod quick_fix;
 mod warning;
@@ -0,0 +1,140 @@
+//! Automatically applicable fixes for diagnostics
+
+use std::ops::Range;
+
+use pomsky_syntax::{
+    diagnose::{DeprecationError, ParseErrorKind},
+    Span,
+};
+
+/// A change that fixes a diagnostic. It can be applied automatically, e.g. by
+/// an editor or with `pomsky --fix`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickFix {
+    /// Short description of what this fix does
+    pub description: String,
+    /// The changes to make, in source order and non-overlapping
+    pub replacements: Vec<Replacement>,
+}
+
+/// Replaces a part of the source code with a string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replacement {
+    /// The part of the source code that is replaced
+    pub span: Span,
+    /// The string to insert in its place
+    pub insert: String,
+}
+
+impl QuickFix {
+    fn replace(source_code: &str, range: Range<usize>, insert: String) -> Self {
+        QuickFix {
+            description: format!("Replace `{}` with `{insert}`", &source_code[range.clone()]),
+            replacements: vec![Replacement { span: Span::from(range), insert }],
+        }
+    }
+}
+
+pub(super) fn get_fix(
+    kind: &ParseErrorKind,
+    source_code: &str,
+    range: Range<usize>,
+) -> Option<QuickFix> {
+    match kind {
+        ParseErrorKind::LexErrorWithMessage(msg) => {
+            let insert = msg.get_replacement(&source_code[range.clone()])?;
+            let insert = match is_in_char_class(source_code, range.start) {
+                true => class_item(insert)?,
+                false => insert,
+            };
+            Some(QuickFix::replace(source_code, range, insert))
+        }
+        ParseErrorKind::Deprecated(DeprecationError::DotInSet) => {
+            // only `[.]` is replaced, not a dot in a larger character class
+            let before = source_code[..range.start].trim_end().strip_suffix('[')?;
+            let after = source_code[range.end..].trim_start().strip_prefix(']')?;
+            if before.trim_end().ends_with('!') {
+                return None;
+            }
+            let range = before.len()..source_code.len() - after.len();
+            Some(QuickFix::replace(source_code, range, ".".into()))
+        }
+        _ => None,
+    }
+}
+
+/// Whether the byte offset is within a character class. Strings and comments
+/// are skipped, since they may contain brackets.
+fn is_in_char_class(source_code: &str, offset: usize) -> bool {
+    let mut in_class = false;
+    let mut chars = source_code[..offset].chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '\\' => {
+                chars.next();
+            }
+            '#' => {
+                chars.find(|&c| c == '\n');
+            }
+            '\'' => {
+                chars.find(|&c| c == '\'');
+            }
+            '"' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    in_class
+}
+
+/// Converts a replacement such as `[w]` to an item of a character class, e.g.
+/// `w`. Returns `None` if it can't be used in a character class.
+fn class_item(insert: String) -> Option<String> {
+    if insert.starts_with("U+") {
+        return Some(insert);
+    }
+    let item = insert.strip_prefix('[')?.strip_suffix(']')?;
+    Some(item.to_string())
+}
+
+#[test]
+fn fixes() {
+    fn fix(input: &str) -> Option<String> {
+        let (_, diagnostics) = crate::Expr::parse(input);
+        let diagnostic = diagnostics.into_iter().next()?;
+        let [Replacement { span, insert }] = &diagnostic.fixes.first()?.replacements[..] else {
+            return None;
+        };
+        let mut fixed = input.to_string();
+        fixed.replace_range(span.range_unchecked(), insert);
+        Some(fixed)
+    }
+
+    assert_eq!(fix("(?:'a')").as_deref(), Some("('a')"));
+    assert_eq!(fix("(?<name>'a')").as_deref(), Some(":name('a')"));
+    assert_eq!(fix("(?<=x)").as_deref(), Some("(<<x)"));
+    assert_eq!(fix("\\d+").as_deref(), Some("[d]+"));
+    assert_eq!(fix("['a' \\d]").as_deref(), Some("['a' d]"));
+    assert_eq!(fix("['[' \\W]").as_deref(), Some("['[' !w]"));
+    assert_eq!(fix("']' \\W").as_deref(), Some("']' [!w]"));
+    assert_eq!(fix("[\\b]"), None);
+    assert_eq!(fix("\\u00E4").as_deref(), Some("U+00E4"));
+    assert_eq!(fix("\\g-1").as_deref(), Some("::-1"));
+    assert_eq!(fix("\\1"), None);
+    assert_eq!(fix("<% 'a' %>").as_deref(), Some("^ 'a' %>"));
+    assert_eq!(fix("'a' [ . ]+").as_deref(), Some("'a' .+"));
+    assert_eq!(fix("[. w]"), None);
+    assert_eq!(fix("![.]"), None);
+}
@@ -21,6 +21,7 @@ impl CompileWarning {
             help: self.kind.help(),
             span: self.span,
             kind: DiagnosticKind::Compat,
+            fixes: vec![],
         }
     }
 }
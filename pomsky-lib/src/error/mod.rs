@@ -1 +1,58 @@
 //! Contains different kinds of errors emitted by Pomsky.
+
+use std::fmt;
+
+use crate::{diagnose::Diagnostic, output::Timings};
+
+/// The error returned by
+/// [`Expr::parse_and_compile_detailed`](crate::Expr::parse_and_compile_detailed)
+/// when a pomsky expression could not be compiled
+#[derive(Debug, Clone)]
+pub struct CompileFailure {
+    /// The errors that prevented compilation. This is never empty.
+    pub errors: Vec<Diagnostic>,
+    /// The warnings emitted before compilation failed
+    pub warnings: Vec<Diagnostic>,
+    /// The time spent in each phase
+    pub timings: Timings,
+}
+
+impl fmt::Display for CompileFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.errors.as_slice() {
+            [] => f.write_str("could not compile expression"),
+            [error] => write!(f, "could not compile expression: {}", error.msg),
+            [error, _] => {
+                write!(f, "could not compile expression: {} (and 1 more error)", error.msg)
+            }
+            [error, rest @ ..] => write!(
+                f,
+                "could not compile expression: {} (and {} more errors)",
+                error.msg,
+                rest.len()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CompileFailure {}
+
+#[test]
+fn test_compile_failure() {
+    use crate::{options::CompileOptions, Expr};
+
+    let failure = Expr::parse_and_compile_detailed("::1", CompileOptions::default())
+        .expect_err("compilation should fail");
+    assert_eq!(failure.errors.len(), 1);
+    assert!(failure.warnings.is_empty());
+    assert_eq!(
+        failure.to_string(),
+        "could not compile expression: Reference to unknown group. There is no group number 1"
+    );
+
+    let error = failure.errors[0].clone();
+    let failure = CompileFailure { errors: vec![error.clone(), error.clone()], ..failure };
+    assert!(failure.to_string().ends_with("There is no group number 1 (and 1 more error)"));
+    let failure = CompileFailure { errors: vec![error.clone(), error.clone(), error], ..failure };
+    assert!(failure.to_string().ends_with("There is no group number 1 (and 2 more errors)"));
+}
@@ -7,7 +7,7 @@ use crate::{
     compile::{CompileResult, CompileState},
     diagnose::CompileError,
     options::{CompileOptions, RegexFlavor},
-    regex::Regex,
+    regex::{CodegenState, Regex, RegexKind},
 };
 
 use super::{Alternation, RuleExt};
@@ -30,13 +30,14 @@ impl<'i> RuleExt<'i> for Alternation<'i> {
         options: CompileOptions,
         state: &mut CompileState<'c, 'i>,
     ) -> CompileResult<'i> {
-        Ok(Regex::Alternation(RegexAlternation {
+        Ok(RegexKind::Alternation(RegexAlternation {
             parts: self
                 .rules
                 .iter()
                 .map(|rule| rule.compile(options, state))
                 .collect::<Result<_, _>>()?,
-        }))
+        })
+        .into())
     }
 
     fn validate(&self, options: &CompileOptions) -> Result<(), CompileError> {
@@ -57,11 +58,32 @@ impl<'i> RegexAlternation<'i> {
         Self { parts }
     }
 
-    pub(crate) fn codegen(&self, buf: &mut String, flavor: RegexFlavor) {
-        for rule in &self.parts {
-            rule.codegen(buf, flavor);
-            buf.push('|');
+    pub(crate) fn codegen(&self, buf: &mut String, state: &mut CodegenState) {
+        if state.is_readable() {
+            // every alternative is on its own line, and lines that continue an
+            // alternative are aligned with it
+            state.increase_indentation(2);
+            for (i, rule) in self.parts.iter().enumerate() {
+                if i > 0 {
+                    state.decrease_indentation(2);
+                    // comments are written before the `|`, which then starts a new line
+                    if !state.comments_before(buf, rule.span) {
+                        state.newline(buf);
+                    }
+                    state.increase_indentation(2);
+                    buf.push_str("| ");
+                }
+                rule.codegen(buf, state);
+            }
+            state.decrease_indentation(2);
+        } else {
+            let separator = if state.flavor == RegexFlavor::Emacs { "\\|" } else { "|" };
+            for (i, rule) in self.parts.iter().enumerate() {
+                if i > 0 {
+                    buf.push_str(separator);
+                }
+                rule.codegen(buf, state);
+            }
         }
-        let _ = buf.pop();
     }
 }
@@ -6,17 +6,24 @@ use pomsky_syntax::exprs::{Boundary, BoundaryKind};
 
 use crate::{
     compile::{CompileResult, CompileState},
-    diagnose::CompileError,
+    diagnose::{CompileError, Feature},
     features::PomskyFeatures,
-    options::CompileOptions,
-    regex::Regex,
+    options::{CompileOptions, RegexFlavor},
+    regex::{CodegenState, RegexKind},
 };
 
 use super::RuleExt;
 
 impl<'i> RuleExt<'i> for Boundary {
-    fn compile<'c>(&'c self, _: CompileOptions, _: &mut CompileState<'c, 'i>) -> CompileResult<'i> {
-        Ok(Regex::Boundary(self.kind))
+    fn compile<'c>(
+        &'c self,
+        options: CompileOptions,
+        _: &mut CompileState<'c, 'i>,
+    ) -> CompileResult<'i> {
+        if matches!(self.kind, BoundaryKind::Word | BoundaryKind::NotWord) {
+            options.require(Feature::WordBoundaries, self.span)?;
+        }
+        Ok(RegexKind::Boundary(self.kind).into())
     }
 
     fn validate(&self, options: &CompileOptions) -> Result<(), CompileError> {
@@ -24,11 +31,21 @@ impl<'i> RuleExt<'i> for Boundary {
     }
 }
 
-pub(crate) fn boundary_kind_codegen(bk: BoundaryKind, buf: &mut String) {
-    match bk {
-        BoundaryKind::Start => buf.push('^'),
-        BoundaryKind::Word => buf.push_str("\\b"),
-        BoundaryKind::NotWord => buf.push_str("\\B"),
-        BoundaryKind::End => buf.push('$'),
+pub(crate) fn boundary_kind_codegen(bk: BoundaryKind, buf: &mut String, state: &mut CodegenState) {
+    if matches!(bk, BoundaryKind::Word | BoundaryKind::NotWord) {
+        state.unicode.shorthands = true;
+    }
+    match (bk, state.flavor) {
+        (BoundaryKind::Start, RegexFlavor::Vim) => buf.push_str("%^"),
+        (BoundaryKind::End, RegexFlavor::Vim) => buf.push_str("%$"),
+        // Vim only has `<` and `>` for the start and end of a word
+        (BoundaryKind::Word, RegexFlavor::Vim) => buf.push_str("%(<|>)"),
+        (BoundaryKind::NotWord, RegexFlavor::Vim) => buf.push_str("%(<|>)@!"),
+        (BoundaryKind::Start, RegexFlavor::Emacs) => buf.push_str("\\`"),
+        (BoundaryKind::End, RegexFlavor::Emacs) => buf.push_str("\\'"),
+        (BoundaryKind::Start, _) => buf.push('^'),
+        (BoundaryKind::Word, _) => buf.push_str("\\b"),
+        (BoundaryKind::NotWord, _) => buf.push_str("\\B"),
+        (BoundaryKind::End, _) => buf.push('$'),
     }
 }
@@ -75,19 +75,28 @@
 //!   When a negated character class only contains 1 item, which is also
 //! negated, the class is   removed and the negations cancel each other out:
 //! `![!w]` = `\w`, `![!L]` = `\p{L}`.
+//!
+//! ### POSIX ERE, Vim and Emacs
+//!
+//! These flavors have no (Unicode-aware) shorthands, so they are emulated with
+//! POSIX classes, e.g. `[w]` = `[[:alnum:]_]` and `![s]` = `[^[:space:]]`.
+//! POSIX classes can't be negated within a character class, so `['a' !s]` is
+//! not supported. A class containing a shorthand and its negation, such as
+//! `[s !s]`, matches any code point: It becomes a dot in ERE, `\_.` in Vim and
+//! `[^z-a]` in Emacs.
 
 use std::collections::HashSet;
 
 use crate::{
     compile::{CompileResult, CompileState},
-    diagnose::{CompileError, CompileErrorKind, Feature},
+    diagnose::{CompileError, CompileErrorKind, Feature, Support},
     exprs::literal,
     options::{CompileOptions, RegexFlavor},
-    regex::{Regex, RegexProperty, RegexShorthand},
+    regex::{CodegenState, RegexKind, RegexProperty, RegexShorthand},
 };
 
 use pomsky_syntax::{
-    exprs::{Category, CharClass, GroupItem, GroupName, OtherProperties},
+    exprs::{Category, CharClass, GroupItem, GroupName, OtherProperties, PosixClass},
     Span,
 };
 
@@ -99,28 +108,41 @@ impl<'i> RuleExt<'i> for CharClass {
         match (self.inner.len(), self.negative) {
             (0, _) => Err(CompileErrorKind::EmptyClass.at(span)),
             (1, false) => match self.inner[0] {
-                GroupItem::Char(c) => Ok(Regex::Char(c)),
-                GroupItem::Range { first, last } => Ok(Regex::CharSet(RegexCharSet {
+                GroupItem::Char(c) => Ok(RegexKind::Char(c).into()),
+                GroupItem::Range { first, last } => Ok(RegexKind::CharSet(RegexCharSet {
                     negative: false,
                     items: vec![RegexCharSetItem::Range { first, last }],
-                })),
+                })
+                .into()),
                 GroupItem::Named { name, negative } => {
-                    named_class_to_regex(name, negative, options.flavor, span)
+                    named_class_to_regex(name, negative, options, span)
                 }
             },
             (1, true) => match self.inner[0] {
-                GroupItem::Char(c) => Ok(Regex::CharSet(RegexCharSet {
+                GroupItem::Char(c) => Ok(RegexKind::CharSet(RegexCharSet {
                     negative: true,
                     items: vec![RegexCharSetItem::Char(c)],
-                })),
-                GroupItem::Range { first, last } => Ok(Regex::CharSet(RegexCharSet {
+                })
+                .into()),
+                GroupItem::Range { first, last } => Ok(RegexKind::CharSet(RegexCharSet {
                     negative: true,
                     items: vec![RegexCharSetItem::Range { first, last }],
-                })),
+                })
+                .into()),
                 GroupItem::Named { name, negative } => {
-                    named_class_to_regex(name, !negative, options.flavor, span)
+                    named_class_to_regex(name, !negative, options, span)
                 }
             },
+            (_, false)
+                if uses_posix_classes(options.flavor) && contains_complement(&self.inner) =>
+            {
+                if options.flavor == RegexFlavor::PosixExtended {
+                    Ok(RegexKind::Dot.into())
+                } else {
+                    // an empty negative class matches any code point
+                    Ok(RegexKind::CharSet(RegexCharSet { negative: true, items: vec![] }).into())
+                }
+            }
             (_, negative) => {
                 let mut prev_group_items: Vec<GroupItem> = vec![];
                 let mut prev_items: HashSet<GroupItem> = HashSet::new();
@@ -147,7 +169,7 @@ impl<'i> RuleExt<'i> for CharClass {
                             named_class_to_regex_class_items(
                                 name,
                                 item_negative,
-                                options.flavor,
+                                options,
                                 span,
                                 &mut buf,
                             )?;
@@ -155,12 +177,61 @@ impl<'i> RuleExt<'i> for CharClass {
                     }
                 }
 
-                Ok(Regex::CharSet(RegexCharSet { negative, items: buf }))
+                Ok(RegexKind::CharSet(RegexCharSet { negative, items: buf }).into())
             }
         }
     }
 }
 
+/// Returns `true` if shorthands are emulated with POSIX classes in this flavor
+fn uses_posix_classes(flavor: RegexFlavor) -> bool {
+    matches!(flavor, RegexFlavor::PosixExtended | RegexFlavor::Vim | RegexFlavor::Emacs)
+}
+
+/// Returns `true` if `[w]` and `[d]` are emulated with Unicode properties in
+/// this flavor, because `\w` and `\d` only match ASCII characters
+fn emulates_shorthands(flavor: RegexFlavor) -> bool {
+    matches!(flavor, RegexFlavor::JavaScript | RegexFlavor::RE2)
+}
+
+/// The Unicode properties matching a word character. RE2 doesn't support
+/// `\p{Alphabetic}`, so letters are used instead.
+fn word_properties(flavor: RegexFlavor) -> [RegexCharSetItem; 4] {
+    let alphabetic = if flavor == RegexFlavor::RE2 {
+        RegexProperty::Category(Category::Letter)
+    } else {
+        RegexProperty::Other(OtherProperties::Alphabetic)
+    };
+    [
+        alphabetic.negative_item(false),
+        RegexProperty::Category(Category::Mark).negative_item(false),
+        RegexProperty::Category(Category::Decimal_Number).negative_item(false),
+        RegexProperty::Category(Category::Connector_Punctuation).negative_item(false),
+    ]
+}
+
+/// The Unicode white space in RE2, where `\s` only matches `[\t\n\f\r ]`
+const RE2_SPACE: [RegexCharSetItem; 4] = [
+    RegexCharSetItem::Shorthand(RegexShorthand::Space),
+    RegexCharSetItem::Char('\x0B'),
+    RegexCharSetItem::Property {
+        negative: false,
+        value: RegexProperty::Category(Category::Separator),
+    },
+    RegexCharSetItem::Char('\u{85}'),
+];
+
+/// Returns `true` if the items contain a named class and its negation, like
+/// `[s !s]`
+fn contains_complement(items: &[GroupItem]) -> bool {
+    items.iter().any(|item| match *item {
+        GroupItem::Named { name, negative: true } => {
+            items.contains(&GroupItem::Named { name, negative: false })
+        }
+        _ => false,
+    })
+}
+
 fn check_char_class_empty(
     item: GroupItem,
     prev_group_items: &[GroupItem],
@@ -188,24 +259,27 @@ fn check_char_class_empty(
 fn named_class_to_regex(
     group: GroupName,
     negative: bool,
-    flavor: RegexFlavor,
+    options: CompileOptions,
     span: Span,
 ) -> CompileResult<'static> {
-    Ok(match group {
+    let flavor = options.flavor;
+    let kind = match group {
+        GroupName::Word | GroupName::Digit | GroupName::Space | GroupName::HorizSpace
+            if uses_posix_classes(flavor) =>
+        {
+            let mut items = vec![];
+            named_class_to_regex_class_items(group, false, options, span, &mut items)?;
+            RegexKind::CharSet(RegexCharSet { negative, items })
+        }
         GroupName::Word => {
-            if flavor == RegexFlavor::JavaScript {
-                Regex::CharSet(RegexCharSet {
+            if emulates_shorthands(flavor) {
+                options.require(Feature::UnicodeProp, span)?;
+                RegexKind::CharSet(RegexCharSet {
                     negative,
-                    items: vec![
-                        RegexProperty::Other(OtherProperties::Alphabetic).negative_item(false),
-                        RegexProperty::Category(Category::Mark).negative_item(false),
-                        RegexProperty::Category(Category::Decimal_Number).negative_item(false),
-                        RegexProperty::Category(Category::Connector_Punctuation)
-                            .negative_item(false),
-                    ],
+                    items: word_properties(flavor).to_vec(),
                 })
             } else {
-                Regex::Shorthand(if negative {
+                RegexKind::Shorthand(if negative {
                     RegexShorthand::NotWord
                 } else {
                     RegexShorthand::Word
@@ -213,21 +287,25 @@ fn named_class_to_regex(
             }
         }
         GroupName::Digit => {
-            if flavor == RegexFlavor::JavaScript {
+            if emulates_shorthands(flavor) {
+                options.require(Feature::UnicodeProp, span)?;
                 RegexProperty::Category(Category::Decimal_Number).negative(negative)
             } else {
-                Regex::Shorthand(if negative {
+                RegexKind::Shorthand(if negative {
                     RegexShorthand::NotDigit
                 } else {
                     RegexShorthand::Digit
                 })
             }
         }
-        GroupName::Space if negative => Regex::Shorthand(RegexShorthand::NotSpace),
-        GroupName::Space => Regex::Shorthand(RegexShorthand::Space),
+        GroupName::Space if flavor == RegexFlavor::RE2 => {
+            RegexKind::CharSet(RegexCharSet { negative, items: RE2_SPACE.to_vec() })
+        }
+        GroupName::Space if negative => RegexKind::Shorthand(RegexShorthand::NotSpace),
+        GroupName::Space => RegexKind::Shorthand(RegexShorthand::Space),
 
         GroupName::HorizSpace | GroupName::VertSpace
-            if matches!(flavor, RegexFlavor::Pcre | RegexFlavor::Java) =>
+            if matches!(flavor, RegexFlavor::Pcre | RegexFlavor::Java | RegexFlavor::Icu) =>
         {
             let shorthand = if group == GroupName::HorizSpace {
                 RegexShorthand::HorizSpace
@@ -236,28 +314,25 @@ fn named_class_to_regex(
             };
 
             if negative {
-                Regex::CharSet(RegexCharSet {
+                RegexKind::CharSet(RegexCharSet {
                     negative: true,
                     items: vec![RegexCharSetItem::Shorthand(shorthand)],
                 })
             } else {
-                Regex::Shorthand(shorthand)
+                RegexKind::Shorthand(shorthand)
             }
         }
-        GroupName::HorizSpace => Regex::CharSet(RegexCharSet {
-            negative,
-            items: vec![
-                RegexCharSetItem::Char('\t'),
-                if flavor == RegexFlavor::Python {
-                    return Err(
-                        CompileErrorKind::Unsupported(Feature::UnicodeProp, flavor).at(span)
-                    );
-                } else {
-                    RegexProperty::Category(Category::Space_Separator).negative_item(false)
-                },
-            ],
-        }),
-        GroupName::VertSpace => Regex::CharSet(RegexCharSet {
+        GroupName::HorizSpace => {
+            options.require(Feature::UnicodeProp, span)?;
+            RegexKind::CharSet(RegexCharSet {
+                negative,
+                items: vec![
+                    RegexCharSetItem::Char('\t'),
+                    RegexProperty::Category(Category::Space_Separator).negative_item(false),
+                ],
+            })
+        }
+        GroupName::VertSpace => RegexKind::CharSet(RegexCharSet {
             negative,
             items: vec![
                 RegexCharSetItem::Range { first: '\x0A', last: '\x0D' },
@@ -267,47 +342,52 @@ fn named_class_to_regex(
             ],
         }),
 
-        _ if flavor == RegexFlavor::Python => {
+        _ if options.supports(Feature::UnicodeProp) == Support::No => {
             return Err(CompileErrorKind::Unsupported(Feature::UnicodeProp, flavor).at(span));
         }
         GroupName::Category(c) => RegexProperty::Category(c).negative(negative),
         GroupName::Script(s) => RegexProperty::Script(s).negative(negative),
-        GroupName::CodeBlock(b) => match flavor {
-            RegexFlavor::DotNet | RegexFlavor::Java | RegexFlavor::Ruby => {
-                RegexProperty::Block(b).negative(negative)
-            }
-            _ => return Err(CompileErrorKind::Unsupported(Feature::UnicodeBlock, flavor).at(span)),
-        },
+        GroupName::CodeBlock(b) => {
+            options.require(Feature::UnicodeBlock, span)?;
+            RegexProperty::Block(b).negative(negative)
+        }
         GroupName::OtherProperties(o) => {
-            if flavor != RegexFlavor::JavaScript && flavor != RegexFlavor::Rust {
-                return Err(CompileErrorKind::Unsupported(Feature::UnicodeProp, flavor).at(span));
-            }
+            options.require(Feature::UnicodeBinaryProp, span)?;
             RegexProperty::Other(o).negative(negative)
         }
-    })
+    };
+    Ok(kind.into())
 }
 
 fn named_class_to_regex_class_items(
     group: GroupName,
     negative: bool,
-    flavor: RegexFlavor,
+    options: CompileOptions,
     span: Span,
     buf: &mut Vec<RegexCharSetItem>,
 ) -> Result<(), CompileError> {
+    let flavor = options.flavor;
     match group {
+        GroupName::Word | GroupName::Digit | GroupName::Space if uses_posix_classes(flavor) => {
+            if negative {
+                options.require(Feature::NegativeShorthandInClass, span)?;
+            }
+            match group {
+                GroupName::Word => {
+                    buf.push(RegexCharSetItem::Posix(PosixClass::Alnum));
+                    buf.push(RegexCharSetItem::Char('_'));
+                }
+                GroupName::Digit => buf.push(RegexCharSetItem::Posix(PosixClass::Digit)),
+                _ => buf.push(RegexCharSetItem::Posix(PosixClass::Space)),
+            }
+        }
         GroupName::Word => {
-            if let RegexFlavor::JavaScript = flavor {
+            if emulates_shorthands(flavor) {
+                options.require(Feature::UnicodeProp, span)?;
                 if negative {
-                    return Err(
-                        CompileErrorKind::Unsupported(Feature::NegativeShorthandW, flavor).at(span)
-                    );
+                    options.require(Feature::NegativeShorthandW, span)?;
                 }
-                buf.push(RegexProperty::Other(OtherProperties::Alphabetic).negative_item(false));
-                buf.push(RegexProperty::Category(Category::Mark).negative_item(false));
-                buf.push(RegexProperty::Category(Category::Decimal_Number).negative_item(false));
-                buf.push(
-                    RegexProperty::Category(Category::Connector_Punctuation).negative_item(false),
-                );
+                buf.extend(word_properties(flavor));
             } else {
                 buf.push(RegexCharSetItem::Shorthand(if negative {
                     RegexShorthand::NotWord
@@ -317,7 +397,8 @@ fn named_class_to_regex_class_items(
             }
         }
         GroupName::Digit => {
-            if flavor == RegexFlavor::JavaScript {
+            if emulates_shorthands(flavor) {
+                options.require(Feature::UnicodeProp, span)?;
                 buf.push(RegexProperty::Category(Category::Decimal_Number).negative_item(negative));
             } else if negative {
                 buf.push(RegexCharSetItem::Shorthand(RegexShorthand::NotDigit));
@@ -325,6 +406,12 @@ fn named_class_to_regex_class_items(
                 buf.push(RegexCharSetItem::Shorthand(RegexShorthand::Digit));
             }
         }
+        GroupName::Space if flavor == RegexFlavor::RE2 => {
+            if negative {
+                options.require(Feature::NegativeShorthandInClass, span)?;
+            }
+            buf.extend(RE2_SPACE);
+        }
         GroupName::Space => buf.push(RegexCharSetItem::Shorthand(if negative {
             RegexShorthand::NotSpace
         } else {
@@ -336,7 +423,7 @@ fn named_class_to_regex_class_items(
         }
 
         GroupName::HorizSpace | GroupName::VertSpace
-            if matches!(flavor, RegexFlavor::Pcre | RegexFlavor::Java) =>
+            if matches!(flavor, RegexFlavor::Pcre | RegexFlavor::Java | RegexFlavor::Icu) =>
         {
             buf.push(RegexCharSetItem::Shorthand(if group == GroupName::HorizSpace {
                 RegexShorthand::HorizSpace
@@ -344,13 +431,13 @@ fn named_class_to_regex_class_items(
                 RegexShorthand::VertSpace
             }));
         }
+        GroupName::HorizSpace if uses_posix_classes(flavor) => {
+            buf.push(RegexCharSetItem::Posix(PosixClass::Blank));
+        }
         GroupName::HorizSpace => {
             buf.push(RegexCharSetItem::Char('\t'));
-            if flavor == RegexFlavor::Python {
-                return Err(CompileErrorKind::Unsupported(Feature::UnicodeProp, flavor).at(span));
-            } else {
-                buf.push(RegexProperty::Category(Category::Space_Separator).negative_item(false));
-            }
+            options.require(Feature::UnicodeProp, span)?;
+            buf.push(RegexProperty::Category(Category::Space_Separator).negative_item(false));
         }
         GroupName::VertSpace => {
             buf.push(RegexCharSetItem::Range { first: '\x0A', last: '\x0D' });
@@ -359,21 +446,17 @@ fn named_class_to_regex_class_items(
             buf.push(RegexCharSetItem::Char('\u{2029}'));
         }
 
-        _ if flavor == RegexFlavor::Python => {
+        _ if options.supports(Feature::UnicodeProp) == Support::No => {
             return Err(CompileErrorKind::Unsupported(Feature::UnicodeProp, flavor).at(span));
         }
         GroupName::Category(c) => buf.push(RegexProperty::Category(c).negative_item(negative)),
         GroupName::Script(s) => buf.push(RegexProperty::Script(s).negative_item(negative)),
-        GroupName::CodeBlock(b) => match flavor {
-            RegexFlavor::DotNet | RegexFlavor::Java | RegexFlavor::Ruby => {
-                buf.push(RegexProperty::Block(b).negative_item(negative));
-            }
-            _ => return Err(CompileErrorKind::Unsupported(Feature::UnicodeBlock, flavor).at(span)),
-        },
+        GroupName::CodeBlock(b) => {
+            options.require(Feature::UnicodeBlock, span)?;
+            buf.push(RegexProperty::Block(b).negative_item(negative));
+        }
         GroupName::OtherProperties(o) => {
-            if flavor != RegexFlavor::JavaScript && flavor != RegexFlavor::Rust {
-                return Err(CompileErrorKind::Unsupported(Feature::UnicodeProp, flavor).at(span));
-            }
+            options.require(Feature::UnicodeBinaryProp, span)?;
             buf.push(RegexProperty::Other(o).negative_item(negative));
         }
     }
@@ -391,7 +474,24 @@ impl RegexCharSet {
         Self { negative: false, items }
     }
 
-    pub(crate) fn codegen(&self, buf: &mut String, flavor: RegexFlavor) {
+    pub(crate) fn new_negative(items: Vec<RegexCharSetItem>) -> Self {
+        Self { negative: true, items }
+    }
+
+    pub(crate) fn codegen(&self, buf: &mut String, state: &mut CodegenState) {
+        match state.flavor {
+            RegexFlavor::PosixExtended | RegexFlavor::Emacs => {
+                return self.codegen_posix(buf, state);
+            }
+            RegexFlavor::Vim if self.negative && self.items.is_empty() => {
+                buf.push_str("\\_.");
+                return;
+            }
+            // without `\_`, a negative class doesn't match line breaks in Vim
+            RegexFlavor::Vim if self.negative => buf.push_str("\\_"),
+            _ => {}
+        }
+
         if self.negative {
             buf.push_str("[^");
         } else {
@@ -402,16 +502,21 @@ impl RegexCharSet {
         for item in &self.items {
             match *item {
                 RegexCharSetItem::Char(c) => {
-                    literal::compile_char_esc_in_class(c, buf, is_first, flavor);
+                    literal::compile_char_esc_in_class(c, buf, is_first, state);
                 }
                 RegexCharSetItem::Range { first, last } => {
-                    literal::compile_char_esc_in_class(first, buf, is_first, flavor);
+                    literal::compile_char_esc_in_class(first, buf, is_first, state);
                     buf.push('-');
-                    literal::compile_char_esc_in_class(last, buf, false, flavor);
+                    literal::compile_char_esc_in_class(last, buf, false, state);
                 }
-                RegexCharSetItem::Shorthand(s) => s.codegen(buf),
+                RegexCharSetItem::Shorthand(s) => s.codegen(buf, state),
                 RegexCharSetItem::Property { negative, value } => {
-                    value.codegen(buf, negative, flavor);
+                    value.codegen(buf, negative, state);
+                }
+                RegexCharSetItem::Posix(class) => {
+                    buf.push_str("[:");
+                    buf.push_str(class.as_str());
+                    buf.push_str(":]");
                 }
             }
             is_first = false;
@@ -419,6 +524,124 @@ impl RegexCharSet {
 
         buf.push(']');
     }
+
+    /// Bracket expressions in POSIX ERE and Emacs have no escapes. Instead,
+    /// `]` must be the first item, `-` the last, and `^` must not be the
+    /// first. `[` must not be followed by `:`, `.` or `=`. In ranges, these
+    /// characters are written as collating symbols in ERE, e.g. `[.-.]`. Emacs
+    /// doesn't support them, so the characters are removed from the range and
+    /// added as separate items.
+    fn codegen_posix(&self, buf: &mut String, state: &mut CodegenState) {
+        if state.flavor == RegexFlavor::Emacs {
+            if self.negative && self.items.is_empty() {
+                buf.push_str("[^z-a]");
+                return;
+            }
+            if let Some(items) = split_special_ranges(&self.items) {
+                return RegexCharSet { negative: self.negative, items }.codegen_posix(buf, state);
+            }
+        }
+
+        fn range_bound(c: char, buf: &mut String, state: &mut CodegenState) {
+            if matches!(c, ']' | '[' | '-' | '^') {
+                buf.push_str("[.");
+                buf.push(c);
+                buf.push_str(".]");
+            } else {
+                literal::compile_char(c, buf, state);
+            }
+        }
+
+        let has_char = |c: char| {
+            self.items.iter().any(|item| matches!(*item, RegexCharSetItem::Char(c2) if c2 == c))
+        };
+
+        buf.push_str(if self.negative { "[^" } else { "[" });
+        let content_start = buf.len();
+
+        if has_char(']') {
+            buf.push(']');
+        }
+        for item in &self.items {
+            match *item {
+                RegexCharSetItem::Char(']' | '[' | '-' | '^') => {}
+                RegexCharSetItem::Char(c) => literal::compile_char(c, buf, state),
+                RegexCharSetItem::Range { first, last } => {
+                    range_bound(first, buf, state);
+                    buf.push('-');
+                    range_bound(last, buf, state);
+                }
+                RegexCharSetItem::Posix(class) => {
+                    buf.push_str("[:");
+                    buf.push_str(class.as_str());
+                    buf.push_str(":]");
+                }
+                // rejected or emulated with POSIX classes during compilation
+                RegexCharSetItem::Shorthand(_) | RegexCharSetItem::Property { .. } => {
+                    unreachable!("shorthands and properties are not supported in POSIX ERE")
+                }
+            }
+        }
+        if has_char('[') {
+            buf.push('[');
+        }
+        let mut dash = has_char('-');
+        if has_char('^') {
+            if buf.len() == content_start && !self.negative {
+                if dash {
+                    buf.push_str("-^");
+                    dash = false;
+                } else {
+                    buf.push_str("[.^.]");
+                }
+            } else {
+                buf.push('^');
+            }
+        }
+        if dash {
+            buf.push('-');
+        }
+
+        buf.push(']');
+    }
+}
+
+/// Removes `]`, `[`, `-` and `^` from the start and end of ranges and adds them
+/// as separate chars. Returns `None` if there is no such range.
+fn split_special_ranges(items: &[RegexCharSetItem]) -> Option<Vec<RegexCharSetItem>> {
+    fn is_special(c: char) -> bool {
+        matches!(c, ']' | '[' | '-' | '^')
+    }
+
+    let has_special_range = items.iter().any(|item| {
+        matches!(*item, RegexCharSetItem::Range { first, last } if is_special(first) || is_special(last))
+    });
+    if !has_special_range {
+        return None;
+    }
+
+    let mut result = Vec::with_capacity(items.len() + 2);
+    for &item in items {
+        let RegexCharSetItem::Range { mut first, mut last } = item else {
+            result.push(item);
+            continue;
+        };
+        // the special characters are ASCII, so their neighbors are valid chars
+        while first <= last && is_special(first) {
+            result.push(RegexCharSetItem::Char(first));
+            first = char::from(first as u8 + 1);
+        }
+        while first <= last && is_special(last) {
+            result.push(RegexCharSetItem::Char(last));
+            last = char::from(last as u8 - 1);
+        }
+        match first.cmp(&last) {
+            std::cmp::Ordering::Less => result.push(RegexCharSetItem::Range { first, last }),
+            std::cmp::Ordering::Equal => result.push(RegexCharSetItem::Char(first)),
+            std::cmp::Ordering::Greater => {}
+        }
+    }
+    Some(result)
 }
 
 #[derive(Clone, Copy)]
@@ -428,6 +651,7 @@ pub(crate) enum RegexCharSetItem {
     Range { first: char, last: char },
     Shorthand(RegexShorthand),
     Property { negative: bool, value: RegexProperty },
+    Posix(PosixClass),
 }
 
 impl RegexCharSetItem {
@@ -435,3 +659,21 @@ impl RegexCharSetItem {
         Self::Range { first, last }
     }
 }
+
+#[test]
+fn test_re2_shorthands() {
+    use crate::Expr;
+
+    let options = CompileOptions { flavor: RegexFlavor::RE2, ..Default::default() };
+    // the `regex` crate has the same syntax as RE2
+    let matches = |input, haystack| {
+        let (regex, _) = Expr::parse_and_compile(input, options);
+        regex::Regex::new(&regex.unwrap()).unwrap().find_iter(haystack).count()
+    };
+
+    assert_eq!(matches("[w]", "é_ß٣\u{301}-"), 5);
+    assert_eq!(matches("![w]", "é_ß٣\u{301}-"), 1);
+    assert_eq!(matches("[d]", "1٣a"), 2);
+    assert_eq!(matches("[s]", " \t\u{b}\u{85}\u{a0}\u{2003}\u{2028}x"), 7);
+    assert_eq!(matches("![s]", " \u{a0}x"), 1);
+}
@@ -5,7 +5,7 @@ use pomsky_syntax::Span;
 
 use crate::{
     compile::CompileResult, diagnose::CompileError, features::PomskyFeatures,
-    options::CompileOptions, regex::Regex,
+    options::CompileOptions, regex::RegexKind,
 };
 
 /// The dot, matching anything except line breaks
@@ -15,7 +15,7 @@ pub(crate) struct Dot {}
 
 impl Dot {
     pub(crate) fn compile(&self, _: CompileOptions) -> CompileResult<'static> {
-        Ok(Regex::Dot)
+        Ok(RegexKind::Dot.into())
     }
 
     pub(crate) fn validate(&self, options: &CompileOptions) -> Result<(), CompileError> {
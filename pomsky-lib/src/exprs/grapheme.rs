@@ -1,14 +1,23 @@
 //! Contains the [`Grapheme`] type, which matches a
 //! [Unicode grapheme](https://www.regular-expressions.info/unicode.html#grapheme).
 
-use pomsky_syntax::Span;
+use pomsky_syntax::{
+    exprs::{Category, OtherProperties, RepetitionKind},
+    Span,
+};
 
 use crate::{
     compile::CompileResult,
-    diagnose::{CompileError, CompileErrorKind, Feature},
+    diagnose::{CompileError, Feature, Support},
+    exprs::{
+        alternation::RegexAlternation,
+        char_class::{RegexCharSet, RegexCharSetItem},
+        group::{RegexGroup, RegexGroupKind},
+        repetition::{RegexQuantifier, RegexRepetition},
+    },
     features::PomskyFeatures,
-    options::{CompileOptions, RegexFlavor},
-    regex::Regex,
+    options::CompileOptions,
+    regex::{Regex, RegexKind, RegexProperty},
 };
 
 /// The `Grapheme` expression, matching a
@@ -19,10 +28,9 @@ pub(crate) struct Grapheme {}
 
 impl Grapheme {
     pub(crate) fn compile(&self, options: CompileOptions) -> CompileResult<'static> {
-        if matches!(options.flavor, RegexFlavor::Pcre | RegexFlavor::Java | RegexFlavor::Ruby) {
-            Ok(Regex::Grapheme)
-        } else {
-            Err(CompileErrorKind::Unsupported(Feature::Grapheme, options.flavor).at(Span::empty()))
+        match options.require(Feature::Grapheme, Span::empty())? {
+            Support::Emulated => Ok(emulate_grapheme()),
+            _ => Ok(RegexKind::Grapheme.into()),
         }
     }
 
@@ -31,3 +39,116 @@ impl Grapheme {
         Ok(())
     }
 }
+
+/// Returns an approximation of an extended grapheme cluster:
+///
+/// ```text
+/// \r\n
+/// | [<control>]
+/// | (?:
+///     \p{Regional_Indicator}{2}
+///   | <hangul syllable>
+///   | \p{Extended_Pictographic}(?:[<extend>]*\u200D\p{Extended_Pictographic})*
+///   | [^<control>]
+///   )[<extend>\u200D]*
+/// ```
+///
+/// where `<control>` is `\p{Cc}\p{Zl}\p{Zp}` and `<extend>` is
+/// `\p{Grapheme_Extend}\p{Emoji_Modifier}\p{Mc}`.
+fn emulate_grapheme() -> Regex<'static> {
+    fn set(items: Vec<RegexCharSetItem>) -> Regex<'static> {
+        RegexKind::CharSet(RegexCharSet::new(items)).into()
+    }
+    fn seq(parts: Vec<Regex<'static>>) -> Regex<'static> {
+        RegexKind::Group(RegexGroup::new(parts, RegexGroupKind::Normal)).into()
+    }
+    fn alt(parts: Vec<Regex<'static>>) -> Regex<'static> {
+        RegexKind::Alternation(RegexAlternation::new(parts)).into()
+    }
+    fn rep(content: Regex<'static>, lower: u32, upper: Option<u32>) -> Regex<'static> {
+        let kind = RepetitionKind::try_from((lower, upper)).unwrap();
+        RegexKind::Repetition(Box::new(RegexRepetition::new(
+            content,
+            kind,
+            RegexQuantifier::Greedy,
+        )))
+        .into()
+    }
+    fn range(first: char, last: char) -> RegexCharSetItem {
+        RegexCharSetItem::range_unchecked(first, last)
+    }
+
+    let control = || {
+        vec![
+            RegexProperty::Category(Category::Control).negative_item(false),
+            RegexProperty::Category(Category::Line_Separator).negative_item(false),
+            RegexProperty::Category(Category::Paragraph_Separator).negative_item(false),
+        ]
+    };
+    let extend = || {
+        vec![
+            RegexProperty::Other(OtherProperties::Grapheme_Extend).negative_item(false),
+            RegexProperty::Other(OtherProperties::Emoji_Modifier).negative_item(false),
+            RegexProperty::Category(Category::Spacing_Mark).negative_item(false),
+        ]
+    };
+    let pictographic =
+        || RegexProperty::Other(OtherProperties::Extended_Pictographic).negative(false).into();
+    let zwj = || RegexKind::Char('\u{200D}').into();
+
+    // Hangul syllables consist of leading consonants (L), vowels (V) and trailing
+    // consonants (T), or are precomposed (LV or LVT)
+    let l = || set(vec![range('\u{1100}', '\u{115F}'), range('\u{A960}', '\u{A97C}')]);
+    let v = || set(vec![range('\u{1160}', '\u{11A7}'), range('\u{D7B0}', '\u{D7C6}')]);
+    let t = || set(vec![range('\u{11A8}', '\u{11FF}'), range('\u{D7CB}', '\u{D7FB}')]);
+    let lv = set(vec![range('\u{AC00}', '\u{D7A3}')]);
+    let hangul = alt(vec![
+        seq(vec![
+            rep(l(), 0, None),
+            alt(vec![rep(v(), 1, None), seq(vec![lv, rep(v(), 0, None)])]),
+            rep(t(), 0, None),
+        ]),
+        rep(l(), 1, None),
+        rep(t(), 1, None),
+    ]);
+
+    let emoji_sequence = seq(vec![
+        pictographic(),
+        rep(seq(vec![rep(set(extend()), 0, None), zwj(), pictographic()]), 0, None),
+    ]);
+    let regional_indicators =
+        RegexProperty::Other(OtherProperties::Regional_Indicator).negative(false).into();
+
+    let mut extend_or_zwj = extend();
+    extend_or_zwj.push(RegexCharSetItem::Char('\u{200D}'));
+
+    alt(vec![
+        RegexKind::Literal("\r\n".into()).into(),
+        set(control()),
+        seq(vec![
+            alt(vec![
+                rep(regional_indicators, 2, Some(2)),
+                hangul,
+                emoji_sequence,
+                RegexKind::CharSet(RegexCharSet::new_negative(control())).into(),
+            ]),
+            rep(set(extend_or_zwj), 0, None),
+        ]),
+    ])
+}
+
+#[test]
+fn test_grapheme_emulation() {
+    use crate::{options::RegexFlavor, Expr};
+
+    let options =
+        CompileOptions { flavor: RegexFlavor::Rust, emulate_grapheme: true, ..Default::default() };
+    let (regex, _) = Expr::parse_and_compile("Grapheme", options);
+    let regex = regex::Regex::new(&regex.unwrap()).unwrap();
+    let clusters = |s| regex.find_iter(s).map(|m| m.as_str()).collect::<Vec<_>>();
+
+    assert_eq!(clusters("e\u{301}a\r\n"), ["e\u{301}", "a", "\r\n"]);
+    assert_eq!(clusters("🇩🇪🇫🇷"), ["🇩🇪", "🇫🇷"]);
+    assert_eq!(clusters("👩\u{200D}👩\u{200D}👧👍🏽"), ["👩\u{200D}👩\u{200D}👧", "👍🏽"]);
+    assert_eq!(clusters("한국\u{1100}\u{1161}\u{11A8}"), ["한", "국", "\u{1100}\u{1161}\u{11A8}"]);
+}
@@ -1,13 +1,17 @@
 use std::collections::HashMap;
 
-use pomsky_syntax::exprs::{Capture, Group, GroupKind};
+use pomsky_syntax::{
+    exprs::{Capture, Group, GroupKind},
+    Span,
+};
 
 use crate::{
     compile::{CompileResult, CompileState},
     diagnose::{CompileError, CompileErrorKind, Feature},
     features::PomskyFeatures,
     options::{CompileOptions, RegexFlavor},
-    regex::Regex,
+    output::GroupInfo,
+    regex::{CodegenState, Regex, RegexKind},
 };
 
 use super::RuleExt;
@@ -54,11 +58,16 @@ impl<'i> RuleExt<'i> for Group<'i> {
         options: CompileOptions,
         state: &mut CompileState<'c, 'i>,
     ) -> CompileResult<'i> {
-        if let GroupKind::Capturing(_) = self.kind {
+        if let GroupKind::Capturing(Capture { name }) = self.kind {
+            state.groups.push(GroupInfo {
+                index: state.next_idx,
+                name: name.map(String::from),
+                span: self.span,
+            });
             state.next_idx += 1;
         }
 
-        Ok(Regex::Group(RegexGroup {
+        Ok(RegexKind::Group(RegexGroup {
             parts: self
                 .parts
                 .iter()
@@ -72,21 +81,15 @@ impl<'i> RuleExt<'i> for Group<'i> {
                 GroupKind::Atomic => RegexGroupKind::Atomic,
                 GroupKind::Normal | GroupKind::Implicit => RegexGroupKind::Normal,
             },
-        }))
+        })
+        .into())
     }
 
     fn validate(&self, options: &CompileOptions) -> Result<(), CompileError> {
         if let GroupKind::Atomic = self.kind {
             options.allowed_features.require(PomskyFeatures::ATOMIC_GROUPS, self.span)?;
 
-            // Python supports atomic groups since 3.11, but it's fairly new, so we forbid
-            // it for now
-            if let RegexFlavor::JavaScript | RegexFlavor::Python | RegexFlavor::Rust =
-                options.flavor
-            {
-                return Err(CompileErrorKind::Unsupported(Feature::AtomicGroups, options.flavor)
-                    .at(self.span));
-            }
+            options.require(Feature::AtomicGroups, self.span)?;
         } else if let GroupKind::Capturing(c) = self.kind {
             let feature = match &c.name {
                 Some(_) => PomskyFeatures::NAMED_GROUPS,
@@ -94,6 +97,10 @@ impl<'i> RuleExt<'i> for Group<'i> {
             };
 
             options.allowed_features.require(feature, self.span)?;
+
+            if c.name.is_some() {
+                options.require(Feature::NamedCaptureGroups, self.span)?;
+            }
         }
 
         for rule in &self.parts {
@@ -123,62 +130,107 @@ impl<'i> RegexGroup<'i> {
         Self { parts, kind: capture }
     }
 
-    pub(crate) fn codegen(&self, buf: &mut String, flavor: RegexFlavor) {
+    pub(crate) fn codegen(&self, buf: &mut String, span: Span, state: &mut CodegenState) {
         match self.kind {
+            // POSIX ERE, Vim and Emacs have no named groups, but they can still be
+            // referenced by number
+            RegexGroupKind::NamedCapture(_) | RegexGroupKind::Capture
+                if matches!(
+                    state.flavor,
+                    RegexFlavor::PosixExtended | RegexFlavor::Vim | RegexFlavor::Emacs
+                ) =>
+            {
+                state.add_capturing_group();
+                let (open, close) = (state.capturing_group(), state.close_group());
+                state.block(buf, open, close, |buf, state| self.codegen_parts(buf, state));
+            }
             RegexGroupKind::NamedCapture(name) => {
+                state.add_capturing_group();
                 // https://www.regular-expressions.info/named.html
-                match flavor {
-                    RegexFlavor::Python | RegexFlavor::Pcre | RegexFlavor::Rust => {
-                        buf.push_str("(?P<");
-                    }
+                let open = match state.flavor {
+                    RegexFlavor::Python
+                    | RegexFlavor::PythonRegex
+                    | RegexFlavor::Pcre
+                    | RegexFlavor::Rust
+                    | RegexFlavor::RE2
+                    | RegexFlavor::PosixExtended
+                    | RegexFlavor::Vim
+                    | RegexFlavor::Emacs => "(?P<",
                     RegexFlavor::DotNet
                     | RegexFlavor::Java
                     | RegexFlavor::Ruby
-                    | RegexFlavor::JavaScript => {
-                        buf.push_str("(?<");
-                    }
-                }
-                buf.push_str(name);
-                buf.push('>');
-                for part in &self.parts {
-                    part.codegen(buf, flavor);
-                }
-                buf.push(')');
+                    | RegexFlavor::JavaScript
+                    | RegexFlavor::Icu => "(?<",
+                };
+                state.block(buf, &format!("{open}{name}>"), ")", |buf, state| {
+                    self.codegen_parts(buf, state);
+                });
             }
             RegexGroupKind::Capture => {
-                buf.push('(');
-                for part in &self.parts {
-                    part.codegen(buf, flavor);
+                state.add_capturing_group();
+                state.block(buf, "(", ")", |buf, state| self.codegen_parts(buf, state));
+            }
+            RegexGroupKind::Atomic if state.flavor == RegexFlavor::Vim => {
+                state.block(buf, "%(", ")@>", |buf, state| self.codegen_parts(buf, state));
+            }
+            // emulated with a lookahead, which is atomic, and a backreference to a hidden group
+            RegexGroupKind::Atomic if state.flavor == RegexFlavor::JavaScript => {
+                if state.in_lookbehind {
+                    state.set_error(
+                        CompileErrorKind::Unsupported(Feature::AtomicGroups, state.flavor).at(span),
+                    );
                 }
-                buf.push(')');
+                let number = state.add_hidden_group();
+                buf.push_str("(?=(");
+                self.codegen_parts(buf, state);
+                buf.push_str("))\\");
+                buf.push_str(&number.to_string());
             }
             RegexGroupKind::Atomic => {
-                buf.push_str("(?>");
-                for part in &self.parts {
-                    part.codegen(buf, flavor);
-                }
-                buf.push(')');
+                state.block(buf, "(?>", ")", |buf, state| self.codegen_parts(buf, state));
             }
-            RegexGroupKind::Normal => {
-                let len = self.parts.len();
-
-                for part in &self.parts {
-                    let needs_parens = len > 1 && part.needs_parens_in_sequence()
-                        || len == 1 && matches!(part, Regex::Unescaped(_));
-                    if needs_parens {
-                        buf.push_str("(?:");
-                    }
-                    part.codegen(buf, flavor);
-                    if needs_parens {
-                        buf.push(')');
-                    }
+            RegexGroupKind::Normal => self.codegen_parts(buf, state),
+        }
+    }
+
+    fn codegen_parts(&self, buf: &mut String, state: &mut CodegenState) {
+        let len = self.parts.len();
+        let mut after_block = false;
+
+        for part in &self.parts {
+            let needs_parens = len > 1 && part.needs_parens_in_sequence()
+                || len == 1
+                    && matches!(part.kind, RegexKind::Unescaped(_))
+                    && matches!(self.kind, RegexGroupKind::Normal)
+                    && !state.is_readable();
+
+            if state.is_readable() {
+                // comments are written before the parentheses around the part
+                state.comments_before(buf, part.span);
+                // parts spanning multiple lines are separated from their neighbors
+                let is_block = part.is_block();
+                if (is_block || after_block) && !CodegenState::is_line_start(buf) {
+                    state.newline(buf);
                 }
+                after_block = is_block;
+            }
+
+            if needs_parens && part.is_block() {
+                let (open, close) = (state.non_capturing_group(), state.close_group());
+                state.block(buf, open, close, |buf, state| part.codegen(buf, state));
+            } else if needs_parens {
+                buf.push_str(state.non_capturing_group());
+                part.codegen(buf, state);
+                buf.push_str(state.close_group());
+            } else {
+                part.codegen(buf, state);
             }
         }
     }
 
     pub(crate) fn needs_parens_before_repetition(&self, flavor: RegexFlavor) -> bool {
         match self.kind {
+            RegexGroupKind::Atomic => flavor == RegexFlavor::JavaScript,
             RegexGroupKind::Normal if self.parts.len() == 1 => {
                 self.parts[0].needs_parens_before_repetition(flavor)
             }
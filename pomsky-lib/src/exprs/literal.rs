@@ -3,14 +3,14 @@ use pomsky_syntax::exprs::Literal;
 use crate::{
     compile::{CompileResult, CompileState},
     options::{CompileOptions, RegexFlavor},
-    regex::Regex,
+    regex::{CodegenState, RegexKind},
 };
 
 use super::RuleExt;
 
 impl<'i> RuleExt<'i> for Literal<'i> {
     fn compile<'c>(&'c self, _: CompileOptions, _: &mut CompileState<'c, 'i>) -> CompileResult<'i> {
-        Ok(Regex::Literal(self.content.clone()))
+        Ok(RegexKind::Literal(self.content.clone()).into())
     }
 }
 
@@ -20,24 +20,54 @@ pub(crate) fn compile_char_esc_in_class(
     c: char,
     buf: &mut String,
     is_first: bool,
-    flavor: RegexFlavor,
+    state: &mut CodegenState,
 ) {
+    use std::fmt::Write;
+
+    let flavor = state.flavor;
     match c {
+        ' ' | '#' if state.is_readable() => {
+            buf.push('\\');
+            buf.push(c);
+        }
+        // in Vim, a backslash before other characters is matched literally
+        '[' if flavor == RegexFlavor::Vim => buf.push_str(r#"\x5B"#),
+        '&' | '|' if flavor == RegexFlavor::Vim => buf.push(c),
+        _ if flavor == RegexFlavor::Vim
+            && c.is_ascii_control()
+            && !matches!(c, '\n' | '\r' | '\t' | '\x1B') =>
+        {
+            write!(buf, "\\x{:02X}", c as u8).unwrap();
+        }
         '\\' => buf.push_str(r#"\\"#),
         '-' => buf.push_str(r#"\-"#),
         '[' => buf.push_str(r#"\["#),
         ']' => buf.push_str(r#"\]"#),
         '^' if is_first => buf.push_str(r#"\^"#),
+        // `&&` is the intersection operator in Java, Ruby and ICU
         '&' if flavor != RegexFlavor::JavaScript => buf.push_str(r#"\&"#),
         '|' if flavor != RegexFlavor::JavaScript => buf.push_str(r#"\|"#),
-        c => compile_char(c, buf, flavor),
+        c => compile_char(c, buf, state),
     }
 }
 
 /// Write a char to the output buffer with proper escaping. Assumes the char is
 /// not in a character class.
-pub(crate) fn codegen_char_esc(c: char, buf: &mut String, flavor: RegexFlavor) {
+pub(crate) fn codegen_char_esc(c: char, buf: &mut String, state: &mut CodegenState) {
     match c {
+        // in POSIX ERE, escaping other characters is undefined
+        ']' | '}' if state.flavor == RegexFlavor::PosixExtended => buf.push(c),
+        // in Emacs, these are only special when preceded by a backslash
+        ']' | '{' | '}' | '(' | ')' | '|' if state.flavor == RegexFlavor::Emacs => buf.push(c),
+        // special in Vim's very magic mode; `/` also ends the pattern in searches
+        '=' | '@' | '%' | '<' | '>' | '&' | '~' | '/' if state.flavor == RegexFlavor::Vim => {
+            buf.push('\\');
+            buf.push(c);
+        }
+        ' ' | '#' if state.is_readable() => {
+            buf.push('\\');
+            buf.push(c);
+        }
         '\\' => buf.push_str(r#"\\"#),
         '[' => buf.push_str(r#"\["#),
         ']' => buf.push_str(r#"\]"#),
@@ -52,20 +82,33 @@ pub(crate) fn codegen_char_esc(c: char, buf: &mut String, flavor: RegexFlavor) {
         '|' => buf.push_str(r#"\|"#),
         '^' => buf.push_str(r#"\^"#),
         '$' => buf.push_str(r#"\$"#),
-        c => compile_char(c, buf, flavor),
+        c => compile_char(c, buf, state),
     }
 }
 
 /// Write a char to the output buffer. This escapes characters that are neither
 /// alphanumeric, nor printable ASCII characters. It does _not_ escape
 /// characters like `(` or `]` that have a special meaning.
-pub(crate) fn compile_char(c: char, buf: &mut String, flavor: RegexFlavor) {
+pub(crate) fn compile_char(c: char, buf: &mut String, state: &mut CodegenState) {
     use std::fmt::Write;
 
+    let flavor = state.flavor;
+    if !c.is_ascii() {
+        state.unicode.code_points = true;
+    }
+
     match c {
+        // POSIX ERE and Emacs have no escape sequences
+        _ if matches!(flavor, RegexFlavor::PosixExtended | RegexFlavor::Emacs) => buf.push(c),
         '\n' => buf.push_str("\\n"),
         '\r' => buf.push_str("\\r"),
         '\t' => buf.push_str("\\t"),
+        '\x1B' if flavor == RegexFlavor::Vim => buf.push_str("\\e"),
+        // `\x` is a shorthand for hex digits in Vim
+        _ if flavor == RegexFlavor::Vim && c.is_ascii_control() => {
+            write!(buf, "%x{:02X}", c as u8).unwrap();
+        }
+        _ if flavor == RegexFlavor::Vim => buf.push(c),
         '\x0C' => buf.push_str("\\f"),
         // not supported in Rust:
         // '\x1B' => buf.push_str("\\e"),
@@ -85,15 +128,23 @@ pub(crate) fn compile_char(c: char, buf: &mut String, flavor: RegexFlavor) {
         _ if c as u32 <= 0xFF && flavor != RegexFlavor::Ruby => {
             write!(buf, "\\x{:02X}", c as u32).unwrap();
         }
-        _ if c as u32 <= 0xFFFF && !matches!(flavor, RegexFlavor::Pcre) => {
+        // RE2 has no `\u` escape
+        _ if c as u32 <= 0xFFFF && !matches!(flavor, RegexFlavor::Pcre | RegexFlavor::RE2) => {
             write!(buf, "\\u{:04X}", c as u32).unwrap();
         }
-        _ if matches!(flavor, RegexFlavor::Python) => {
+        _ if matches!(flavor, RegexFlavor::Python | RegexFlavor::PythonRegex) => {
             write!(buf, "\\U{:08X}", c as u32).unwrap();
         }
         _ => {
+            state.unicode.braced_escapes = true;
             match flavor {
-                RegexFlavor::Pcre | RegexFlavor::Java | RegexFlavor::Ruby => buf.push_str("\\x"),
+                RegexFlavor::Pcre
+                | RegexFlavor::Java
+                | RegexFlavor::Ruby
+                | RegexFlavor::RE2
+                | RegexFlavor::Icu => {
+                    buf.push_str("\\x");
+                }
                 _ => buf.push_str("\\u"),
             }
             write!(buf, "{{{:X}}}", c as u32).unwrap();
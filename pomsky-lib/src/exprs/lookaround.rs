@@ -4,10 +4,10 @@ use pomsky_syntax::exprs::{Lookaround, LookaroundKind};
 
 use crate::{
     compile::{CompileResult, CompileState},
-    diagnose::{CompatWarning, CompileError, CompileErrorKind, CompileWarningKind, Feature},
+    diagnose::{CompatWarning, CompileError, CompileWarningKind, Feature, Support},
     features::PomskyFeatures,
     options::{CompileOptions, RegexFlavor},
-    regex::Regex,
+    regex::{CodegenState, Regex, RegexKind},
 };
 
 use super::RuleExt;
@@ -27,27 +27,23 @@ impl<'i> RuleExt<'i> for Lookaround<'i> {
         options: CompileOptions,
         state: &mut CompileState<'c, 'i>,
     ) -> CompileResult<'i> {
-        match options.flavor {
-            RegexFlavor::Rust => {
-                return Err(CompileErrorKind::Unsupported(Feature::Lookaround, options.flavor)
-                    .at(self.span));
-            }
-            RegexFlavor::JavaScript
-                if matches!(self.kind, LookaroundKind::Behind | LookaroundKind::BehindNegative) =>
-            {
-                state.diagnostics.push(
-                    CompileWarningKind::Compat(CompatWarning::JsLookbehind)
-                        .at(self.span)
-                        .diagnostic(),
-                );
-            }
-            _ => (),
+        let is_lookbehind =
+            matches!(self.kind, LookaroundKind::Behind | LookaroundKind::BehindNegative);
+
+        options.require(Feature::Lookaround, self.span)?;
+        if is_lookbehind && options.require(Feature::Lookbehind, self.span)? == Support::Warn {
+            // only JavaScript, where not all browsers support it
+            state.diagnostics.push(
+                CompileWarningKind::Compat(CompatWarning::JsLookbehind).at(self.span).diagnostic(),
+            );
+        }
+
+        let content = self.rule.compile(options, state)?;
+        if is_lookbehind && !content.has_bounded_length() {
+            options.require(Feature::UnboundedLookbehind, self.span)?;
         }
 
-        Ok(Regex::Lookaround(Box::new(RegexLookaround {
-            content: self.rule.compile(options, state)?,
-            kind: self.kind,
-        })))
+        Ok(RegexKind::Lookaround(Box::new(RegexLookaround { content, kind: self.kind })).into())
     }
 
     fn validate(&self, options: &CompileOptions) -> Result<(), CompileError> {
@@ -66,14 +62,30 @@ pub(crate) struct RegexLookaround<'i> {
 }
 
 impl<'i> RegexLookaround<'i> {
-    pub(crate) fn codegen(&self, buf: &mut String, flavor: RegexFlavor) {
-        buf.push_str(match self.kind {
+    pub(crate) fn codegen(&self, buf: &mut String, state: &mut CodegenState) {
+        // in Vim, lookarounds are written as a group followed by `@=`, `@!`, `@<=` or `@<!`
+        if state.flavor == RegexFlavor::Vim {
+            let close = match self.kind {
+                LookaroundKind::Ahead => ")@=",
+                LookaroundKind::Behind => ")@<=",
+                LookaroundKind::AheadNegative => ")@!",
+                LookaroundKind::BehindNegative => ")@<!",
+            };
+            state.block(buf, "%(", close, |buf, state| self.content.codegen(buf, state));
+            return;
+        }
+
+        let is_lookbehind =
+            matches!(self.kind, LookaroundKind::Behind | LookaroundKind::BehindNegative);
+        let was_in_lookbehind = std::mem::replace(&mut state.in_lookbehind, is_lookbehind);
+
+        let open = match self.kind {
             LookaroundKind::Ahead => "(?=",
             LookaroundKind::Behind => "(?<=",
             LookaroundKind::AheadNegative => "(?!",
             LookaroundKind::BehindNegative => "(?<!",
-        });
-        self.content.codegen(buf, flavor);
-        buf.push(')');
+        };
+        state.block(buf, open, ")", |buf, state| self.content.codegen(buf, state));
+        state.in_lookbehind = was_in_lookbehind;
     }
 }
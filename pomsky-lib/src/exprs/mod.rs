@@ -2,9 +2,12 @@ use std::collections::HashMap;
 
 use crate::{
     compile::{CompileResult, CompileState},
-    diagnose::{CompileError, Diagnostic},
-    options::CompileOptions,
-    regex::Count,
+    diagnose::{CompileError, CompileErrorKind, Diagnostic, Feature, Severity},
+    error::CompileFailure,
+    options::{CompileOptions, OutputStyle, RegexFlavor},
+    output::{CompileOutput, Compiled, FlavorCheck, GroupInfo, RegexFlags, Stopwatch, Timings},
+    regex::{CodegenState, Count, Regex},
+    source_map::SourceMap,
 };
 
 pub(crate) mod alternation;
@@ -67,15 +70,164 @@ impl<'i> Expr<'i> {
         input: &'i str,
         options: CompileOptions,
     ) -> (Option<String>, Vec<Diagnostic>) {
-        if let Err(e) = self.0.validate(&options) {
-            return (None, vec![e.diagnostic(input)]);
+        let (output, diagnostics) =
+            self.compile_inner(input, options, false, &mut Timings::default());
+        (output.map(|(output, _)| output.regex), diagnostics)
+    }
+
+    /// Compile a `Expr` that has been parsed, to a regex.
+    ///
+    /// Unlike [`Expr::compile`], this separates errors from warnings, and
+    /// returns information about the regex, such as its capturing groups. The
+    /// time spent parsing is zero; use [`Expr::parse_and_compile_detailed`] to
+    /// measure it as well.
+    pub fn compile_detailed(
+        &self,
+        input: &'i str,
+        options: CompileOptions,
+    ) -> Result<Compiled, CompileFailure> {
+        let mut timings = Timings::default();
+        let (output, diagnostics) = self.compile_inner(input, options, false, &mut timings);
+        Self::detailed_result(output.map(|(output, _)| output), diagnostics, timings)
+    }
+
+    /// Compile a `Expr` that has been parsed, to a regex. In addition to the
+    /// regex, a [`SourceMap`] is returned, which maps parts of the regex to
+    /// the spans in the pomsky expression they were compiled from.
+    pub fn compile_with_source_map(
+        &self,
+        input: &'i str,
+        options: CompileOptions,
+    ) -> (Option<(String, SourceMap)>, Vec<Diagnostic>) {
+        let (output, diagnostics) =
+            self.compile_inner(input, options, true, &mut Timings::default());
+        (output.map(|(output, map)| (output.regex, map.unwrap_or_default())), diagnostics)
+    }
+
+    /// Compile a `Expr` that has been parsed, once for every [`RegexFlavor`].
+    ///
+    /// This shows which flavors support the expression, which ones emit
+    /// warnings, and which regex features prevent compilation for the others.
+    /// The `flavor` in the options is ignored, and the `flavor_version` is only
+    /// used for the flavor it belongs to.
+    pub fn check_all_flavors(&self, input: &'i str, options: CompileOptions) -> Vec<FlavorCheck> {
+        RegexFlavor::ALL
+            .iter()
+            .map(|&flavor| {
+                let flavor_version = options.flavor_version.filter(|v| v.flavor() == flavor);
+                let options = CompileOptions { flavor, flavor_version, ..options };
+                let mut timings = Timings::default();
+                let (output, mut diagnostics) =
+                    self.compile_raw(input, options, false, &mut timings);
+                let (output, unsupported_feature) = match output {
+                    Ok((output, _)) => (Some(output), None),
+                    Err(error) => {
+                        diagnostics.insert(0, error.diagnostic(input));
+                        (None, error.unsupported_feature())
+                    }
+                };
+                let result = Self::detailed_result(output, diagnostics, timings);
+                FlavorCheck { flavor, result, unsupported_feature }
+            })
+            .collect()
+    }
+
+    fn compile_inner(
+        &self,
+        input: &'i str,
+        options: CompileOptions,
+        create_source_map: bool,
+        timings: &mut Timings,
+    ) -> (Option<(CompileOutput, Option<SourceMap>)>, Vec<Diagnostic>) {
+        match self.compile_raw(input, options, create_source_map, timings) {
+            (Ok(output), diagnostics) => (Some(output), diagnostics),
+            (Err(error), mut diagnostics) => {
+                diagnostics.insert(0, error.diagnostic(input));
+                (None, diagnostics)
+            }
+        }
+    }
+
+    /// Compiles the expression and returns the error, if any, together with
+    /// the warnings
+    #[allow(clippy::type_complexity)]
+    fn compile_raw(
+        &self,
+        input: &'i str,
+        options: CompileOptions,
+        create_source_map: bool,
+        timings: &mut Timings,
+    ) -> (Result<(CompileOutput, Option<SourceMap>), CompileError>, Vec<Diagnostic>) {
+        let compile_start = Stopwatch::start();
+        let (compiled, count, mut groups, diagnostics) = match self.compile_to_regex(options) {
+            Ok(res) => res,
+            Err(error) => {
+                timings.compile = compile_start.elapsed();
+                return (Err(error), vec![]);
+            }
+        };
+        timings.compile = compile_start.elapsed();
+
+        let codegen_start = Stopwatch::start();
+        let mut buf = String::new();
+        let mut codegen_state = CodegenState::new(options.flavor, options.output_style);
+        if create_source_map {
+            codegen_state = codegen_state.with_source_map();
         }
 
+        if count != Count::Zero {
+            if codegen_state.is_readable() {
+                buf.push_str("(?x)\n");
+                codegen_state = codegen_state.with_comments(input);
+            }
+            if options.flavor == RegexFlavor::Vim {
+                // enable "very magic" mode, where special characters don't need a backslash
+                buf.push_str("\\v");
+            }
+            compiled.codegen(&mut buf, &mut codegen_state);
+            codegen_state.remaining_comments(&mut buf);
+        }
+        if let Some(error) = codegen_state.take_error() {
+            timings.codegen = codegen_start.elapsed();
+            return (Err(error), diagnostics);
+        }
+        for group in &mut groups {
+            group.index = codegen_state.group_number(group.index);
+        }
+
+        let source_map = codegen_state.take_source_map().map(SourceMap::new);
+        let flags = RegexFlags::new(options.flavor, codegen_state.unicode);
+        let output = CompileOutput { regex: buf, groups, flags };
+        timings.codegen = codegen_start.elapsed();
+
+        (Ok((output, source_map)), diagnostics)
+    }
+
+    /// Validates and compiles the expression to a [`Regex`] and optimizes it.
+    /// Also returns the capturing groups and the warnings.
+    #[allow(clippy::type_complexity)]
+    fn compile_to_regex(
+        &self,
+        options: CompileOptions,
+    ) -> Result<(Regex<'i>, Count, Vec<GroupInfo>, Vec<Diagnostic>), CompileError> {
+        if options.output_style == OutputStyle::Readable {
+            options.require(Feature::FreeSpacing, self.0.span())?;
+        }
+        if let Some(version) = options.flavor_version {
+            if version.flavor() != options.flavor {
+                return Err(CompileErrorKind::FlavorVersionMismatch(
+                    version.flavor(),
+                    options.flavor,
+                )
+                .at(self.0.span()));
+            }
+        }
+
+        self.0.validate(&options)?;
+
         let mut used_names = HashMap::new();
         let mut groups_count = 0;
-        if let Err(e) = self.0.get_capturing_groups(&mut groups_count, &mut used_names, false) {
-            return (None, vec![e.diagnostic(input)]);
-        }
+        self.0.get_capturing_groups(&mut groups_count, &mut used_names, false)?;
 
         let no_span = Span::empty();
 
@@ -101,17 +253,10 @@ impl<'i> Expr<'i> {
 
         let mut state =
             CompileState::new(RegexQuantifier::Greedy, used_names, groups_count, builtins);
-        let mut compiled = match self.0.compile(options, &mut state) {
-            Ok(compiled) => compiled,
-            Err(e) => return (None, vec![e.diagnostic(input)]),
-        };
+        let mut compiled = self.0.compile(options, &mut state)?;
         let count = compiled.optimize();
 
-        let mut buf = String::new();
-        if count != Count::Zero {
-            compiled.codegen(&mut buf, options.flavor);
-        }
-        (Some(buf), state.diagnostics)
+        Ok((compiled, count, state.groups, state.diagnostics))
     }
 
     /// Parse a string to a `Expr` and compile it to a regex.
@@ -119,8 +264,57 @@ impl<'i> Expr<'i> {
         input: &'i str,
         options: CompileOptions,
     ) -> (Option<String>, Vec<Diagnostic>) {
+        Self::parse_and(input, |parsed| parsed.compile(input, options))
+    }
+
+    /// Parse a string to a `Expr` and compile it to a regex.
+    ///
+    /// Unlike [`Expr::parse_and_compile`], this separates errors from
+    /// warnings, and returns information about the regex, such as its capturing
+    /// groups.
+    pub fn parse_and_compile_detailed(
+        input: &'i str,
+        options: CompileOptions,
+    ) -> Result<Compiled, CompileFailure> {
+        let mut timings = Timings::default();
+
+        let parse_start = Stopwatch::start();
+        let (parsed, parse_diagnostics) = Self::parse(input);
+        let mut diagnostics = parse_diagnostics.collect::<Vec<_>>();
+        timings.parse = parse_start.elapsed();
+
+        let output = match parsed {
+            Some(parsed) => {
+                let (output, compile_diagnostics) =
+                    parsed.compile_inner(input, options, false, &mut timings);
+                diagnostics.extend(compile_diagnostics);
+                output.map(|(output, _)| output)
+            }
+            None => None,
+        };
+        Self::detailed_result(output, diagnostics, timings)
+    }
+
+    fn detailed_result(
+        output: Option<CompileOutput>,
+        diagnostics: Vec<Diagnostic>,
+        timings: Timings,
+    ) -> Result<Compiled, CompileFailure> {
+        let (errors, warnings) =
+            diagnostics.into_iter().partition(|d| matches!(d.severity, Severity::Error));
+
+        match output {
+            Some(output) => Ok(Compiled { output, warnings, timings }),
+            None => Err(CompileFailure { errors, warnings, timings }),
+        }
+    }
+
+    fn parse_and<T>(
+        input: &'i str,
+        compile: impl FnOnce(&Self) -> (Option<T>, Vec<Diagnostic>),
+    ) -> (Option<T>, Vec<Diagnostic>) {
         match Self::parse(input) {
-            (Some(parsed), warnings1) => match parsed.compile(input, options) {
+            (Some(parsed), warnings1) => match compile(&parsed) {
                 (Some(compiled), warnings2) => {
                     let mut diagnostics =
                         Vec::with_capacity(warnings1.size_hint().0 + warnings2.len());
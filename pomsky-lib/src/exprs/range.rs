@@ -7,7 +7,7 @@ use crate::{
     diagnose::{CompileError, CompileErrorKind},
     features::PomskyFeatures,
     options::CompileOptions,
-    regex::Regex,
+    regex::{Regex, RegexKind},
 };
 
 use super::{
@@ -412,7 +412,7 @@ impl Rule {
 
     fn to_regex(&self) -> Regex<'static> {
         match self {
-            Rule::Empty => Regex::Literal(Cow::Borrowed("")),
+            Rule::Empty => RegexKind::Literal(Cow::Borrowed("")).into(),
             Rule::Class(c) => c.to_regex(),
             Rule::Repeat(r) => r.to_regex(),
             Rule::Alt(a) => a.to_regex(),
@@ -445,11 +445,12 @@ struct Repeat {
 
 impl Repeat {
     fn to_regex(&self) -> Regex<'static> {
-        Regex::Repetition(Box::new(RegexRepetition::new(
+        RegexKind::Repetition(Box::new(RegexRepetition::new(
             self.rule.to_regex(),
             RepetitionKind::try_from((self.min as u32, Some(self.max as u32))).unwrap(),
             RegexQuantifier::Greedy,
         )))
+        .into()
     }
 }
 
@@ -458,17 +459,19 @@ struct Alt(Vec<Vec<Rule>>);
 
 impl Alt {
     fn to_regex(&self) -> Regex<'static> {
-        Regex::Alternation(RegexAlternation::new(
+        RegexKind::Alternation(RegexAlternation::new(
             self.0
                 .iter()
                 .map(|v| {
-                    Regex::Group(RegexGroup::new(
+                    RegexKind::Group(RegexGroup::new(
                         v.iter().map(|r| r.to_regex()).collect(),
                         RegexGroupKind::Normal,
                     ))
+                    .into()
                 })
                 .collect(),
         ))
+        .into()
     }
 }
 
@@ -476,8 +479,8 @@ impl Class {
     fn to_regex(self) -> Regex<'static> {
         let (a, b) = (self.start, self.end);
 
-        Regex::CharSet(RegexCharSet::new(match (a, b, a == b) {
-            (0..=9, _, true) => return Regex::Char((a + b'0') as char),
+        RegexKind::CharSet(RegexCharSet::new(match (a, b, a == b) {
+            (0..=9, _, true) => return RegexKind::Char((a + b'0') as char).into(),
             (0..=9, 0..=9, _) => {
                 vec![RegexCharSetItem::range_unchecked((a + b'0') as char, (b + b'0') as char)]
             }
@@ -510,5 +513,6 @@ impl Class {
                 RegexCharSetItem::range_unchecked('A', (b + b'A' - 10) as char),
             ],
         }))
+        .into()
     }
 }
@@ -1,11 +1,14 @@
-use pomsky_syntax::exprs::{Reference, ReferenceTarget};
+use pomsky_syntax::{
+    exprs::{Reference, ReferenceTarget},
+    Span,
+};
 
 use crate::{
     compile::{CompileResult, CompileState},
-    diagnose::{CompileError, CompileErrorKind, Feature},
+    diagnose::{CompileError, CompileErrorKind, Feature, Support},
     features::PomskyFeatures,
     options::{CompileOptions, RegexFlavor},
-    regex::Regex,
+    regex::{CodegenState, RegexKind},
 };
 
 use super::RuleExt;
@@ -76,41 +79,30 @@ impl<'i> RuleExt<'i> for Reference<'i> {
             }
         };
 
-        match options.flavor {
-            RegexFlavor::Rust => Err(CompileErrorKind::Unsupported(
-                if direction == ReferenceDirection::Backwards {
-                    Feature::Backreference
-                } else {
-                    Feature::ForwardReference
-                },
-                options.flavor,
-            )
-            .at(self.span)),
+        let feature = match direction {
+            ReferenceDirection::Backwards => Feature::Backreference,
+            ReferenceDirection::Forwards => Feature::ForwardReference,
+        };
+        options.require(feature, self.span)?;
 
-            RegexFlavor::JavaScript | RegexFlavor::Python
-                if direction == ReferenceDirection::Forwards =>
-            {
-                Err(CompileErrorKind::Unsupported(Feature::ForwardReference, options.flavor)
-                    .at(self.span))
-            }
+        // some flavors only support `\1` to `\9`. In ERE, the group number can only increase,
+        // because it has no non-capturing groups; this is checked again during codegen
+        if number > 9 {
+            options.require(Feature::BackreferenceAbove9, self.span)?;
+        }
 
-            _ => Ok(Regex::Reference(match options.flavor {
-                RegexFlavor::Ruby => {
-                    if let Some(group_name) = state.used_names_vec[number as usize].as_ref() {
-                        RegexReference::Name(group_name.clone())
-                    } else if !state.has_named {
-                        RegexReference::Number(number)
-                    } else {
-                        return Err(CompileErrorKind::Unsupported(
-                            Feature::MixedReferences,
-                            options.flavor,
-                        )
-                        .at(self.span));
-                    }
+        let reference = match state.used_names_vec[number as usize].as_ref() {
+            Some(group_name) if options.flavor == RegexFlavor::Ruby => {
+                RegexReference::Name(group_name.clone())
+            }
+            _ => {
+                if state.has_named {
+                    options.require(Feature::MixedReferences, self.span)?;
                 }
-                _ => RegexReference::Number(number),
-            })),
-        }
+                RegexReference::Number(number)
+            }
+        };
+        Ok(RegexKind::Reference(reference).into())
     }
 
     fn validate(&self, options: &CompileOptions) -> Result<(), CompileError> {
@@ -125,12 +117,25 @@ pub(crate) enum RegexReference {
 }
 
 impl RegexReference {
-    pub(crate) fn codegen(&self, buf: &mut String, _: RegexFlavor) {
+    pub(crate) fn codegen(&self, buf: &mut String, span: Span, state: &mut CodegenState) {
         use std::fmt::Write;
 
         match self {
             &RegexReference::Number(number) => {
-                debug_assert!(number <= 99);
+                let number = state.group_number(number);
+                if state.flavor.supports(Feature::BackreferenceAbove9) == Support::No {
+                    if number > 9 {
+                        state.set_error(
+                            CompileErrorKind::Unsupported(
+                                Feature::BackreferenceAbove9,
+                                state.flavor,
+                            )
+                            .at(span),
+                        );
+                    }
+                } else {
+                    debug_assert!(number <= 99);
+                }
                 write!(buf, "\\{number}").unwrap();
             }
             RegexReference::Name(name) => {
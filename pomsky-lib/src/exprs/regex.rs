@@ -5,14 +5,14 @@ use crate::{
     diagnose::CompileError,
     features::PomskyFeatures,
     options::CompileOptions,
-    regex::Regex,
+    regex::RegexKind,
 };
 
 use super::RuleExt;
 
 impl<'i> RuleExt<'i> for RegexLiteral<'i> {
     fn compile<'c>(&'c self, _: CompileOptions, _: &mut CompileState<'c, 'i>) -> CompileResult<'i> {
-        Ok(Regex::Unescaped(self.content.clone()))
+        Ok(RegexKind::Unescaped(self.content.clone()).into())
     }
 
     fn validate(&self, options: &CompileOptions) -> Result<(), CompileError> {
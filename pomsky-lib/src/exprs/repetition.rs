@@ -4,9 +4,9 @@ use pomsky_syntax::exprs::{Quantifier, Repetition, RepetitionKind};
 
 use crate::{
     compile::{CompileResult, CompileState},
-    diagnose::{CompileError, CompileErrorKind, Feature},
+    diagnose::{CompileError, Feature},
     options::{CompileOptions, RegexFlavor},
-    regex::Regex,
+    regex::{CodegenState, Regex, RegexKind},
 };
 
 use super::RuleExt;
@@ -28,9 +28,8 @@ impl<'i> RuleExt<'i> for Repetition<'i> {
     ) -> CompileResult<'i> {
         let content = self.rule.compile(options, state)?;
 
-        if options.flavor == RegexFlavor::Ruby && content.is_assertion() {
-            return Err(CompileErrorKind::Unsupported(Feature::RepeatedAssertion, options.flavor)
-                .at(self.span));
+        if content.is_assertion() {
+            options.require(Feature::RepeatedAssertion, self.span)?;
         }
 
         let quantifier = match self.quantifier {
@@ -39,7 +38,26 @@ impl<'i> RuleExt<'i> for Repetition<'i> {
             Quantifier::Default => state.default_quantifier,
         };
 
-        Ok(Regex::Repetition(Box::new(RegexRepetition { content, kind: self.kind, quantifier })))
+        // a lazy quantifier with a fixed number of repetitions is emitted as greedy
+        if quantifier == RegexQuantifier::Lazy
+            && Some(self.kind.lower_bound) != self.kind.upper_bound
+        {
+            options.require(Feature::LazyQuantifiers, self.span)?;
+            // Emacs only supports `*?`, `+?` and `??`
+            if !matches!(
+                (self.kind.lower_bound, self.kind.upper_bound),
+                (0 | 1, None) | (0, Some(1))
+            ) {
+                options.require(Feature::LazyBoundedRepetition, self.span)?;
+            }
+        }
+
+        Ok(RegexKind::Repetition(Box::new(RegexRepetition {
+            content,
+            kind: self.kind,
+            quantifier,
+        }))
+        .into())
     }
 
     fn validate(&self, options: &CompileOptions) -> Result<(), CompileError> {
@@ -69,21 +87,34 @@ impl<'i> RegexRepetition<'i> {
         Self { content, kind, quantifier }
     }
 
-    pub(crate) fn codegen(&self, buf: &mut String, flavor: RegexFlavor) {
+    pub(crate) fn codegen(&self, buf: &mut String, state: &mut CodegenState) {
         use std::fmt::Write;
 
-        if let Regex::Literal(Cow::Borrowed("")) = self.content {
+        if let RegexKind::Literal(Cow::Borrowed("")) = self.content.kind {
             return;
         }
 
-        if self.content.needs_parens_before_repetition(flavor) {
-            buf.push_str("(?:");
-            self.content.codegen(buf, flavor);
-            buf.push(')');
+        if self.content.needs_parens_before_repetition(state.flavor) {
+            if self.content.is_block() {
+                let (open, close) = (state.non_capturing_group(), state.close_group());
+                state.block(buf, open, close, |buf, state| self.content.codegen(buf, state));
+            } else {
+                buf.push_str(state.non_capturing_group());
+                self.content.codegen(buf, state);
+                buf.push_str(state.close_group());
+            }
         } else {
-            self.content.codegen(buf, flavor);
+            self.content.codegen(buf, state);
+        }
+
+        if state.flavor == RegexFlavor::Vim {
+            return self.codegen_vim_quantifier(buf);
         }
 
+        // Emacs uses `\{...\}` for bounded repetitions
+        let (open, close) =
+            if state.flavor == RegexFlavor::Emacs { ("\\{", "\\}") } else { ("{", "}") };
+
         let omit_lazy = match self.kind {
             RepetitionKind { lower_bound: 1, upper_bound: Some(1) } => return,
             RepetitionKind { lower_bound: 0, upper_bound: Some(1) } => {
@@ -99,21 +130,21 @@ impl<'i> RegexRepetition<'i> {
                 false
             }
             RepetitionKind { lower_bound, upper_bound: None } => {
-                write!(buf, "{{{lower_bound},}}").unwrap();
+                write!(buf, "{open}{lower_bound},{close}").unwrap();
                 false
             }
             RepetitionKind { lower_bound, upper_bound: Some(upper_bound) }
                 if lower_bound == upper_bound =>
             {
-                write!(buf, "{{{lower_bound}}}").unwrap();
+                write!(buf, "{open}{lower_bound}{close}").unwrap();
                 true
             }
             RepetitionKind { lower_bound: 0, upper_bound: Some(upper_bound) } => {
-                write!(buf, "{{0,{upper_bound}}}").unwrap();
+                write!(buf, "{open}0,{upper_bound}{close}").unwrap();
                 false
             }
             RepetitionKind { lower_bound, upper_bound: Some(upper_bound) } => {
-                write!(buf, "{{{lower_bound},{upper_bound}}}").unwrap();
+                write!(buf, "{open}{lower_bound},{upper_bound}{close}").unwrap();
                 false
             }
         };
@@ -124,4 +155,32 @@ impl<'i> RegexRepetition<'i> {
             }
         }
     }
+
+    /// Vim's very magic mode uses `=` instead of `?`, and lazy quantifiers are
+    /// written as `{-n,m}`.
+    fn codegen_vim_quantifier(&self, buf: &mut String) {
+        use std::fmt::Write;
+
+        let RepetitionKind { lower_bound, upper_bound } = self.kind;
+        if self.quantifier == RegexQuantifier::Lazy && Some(lower_bound) != upper_bound {
+            match upper_bound {
+                None if lower_bound == 0 => buf.push_str("{-}"),
+                None => write!(buf, "{{-{lower_bound},}}").unwrap(),
+                Some(upper_bound) => write!(buf, "{{-{lower_bound},{upper_bound}}}").unwrap(),
+            }
+            return;
+        }
+
+        match (lower_bound, upper_bound) {
+            (1, Some(1)) => {}
+            (0, Some(1)) => buf.push('='),
+            (0, None) => buf.push('*'),
+            (1, None) => buf.push('+'),
+            (_, None) => write!(buf, "{{{lower_bound},}}").unwrap(),
+            (_, Some(upper_bound)) if lower_bound == upper_bound => {
+                write!(buf, "{{{lower_bound}}}").unwrap();
+            }
+            (_, Some(upper_bound)) => write!(buf, "{{{lower_bound},{upper_bound}}}").unwrap(),
+        }
+    }
 }
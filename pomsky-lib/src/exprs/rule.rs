@@ -45,7 +45,7 @@ impl<'i> RuleExt<'i> for Rule<'i> {
         options: CompileOptions,
         state: &mut CompileState<'c, 'i>,
     ) -> CompileResult<'i> {
-        match self {
+        let mut regex = match self {
             Rule::Literal(l) => l.compile(options, state),
             Rule::CharClass(c) => c.compile(options, state),
             Rule::Group(g) => g.compile(options, state),
@@ -63,7 +63,13 @@ impl<'i> RuleExt<'i> for Rule<'i> {
             Rule::Range(r) => r.compile(options, state),
             Rule::Regex(r) => r.compile(options, state),
             Rule::StmtExpr(m) => m.compile(options, state),
+        }?;
+
+        // keep the span of a nested rule, e.g. the content of a variable
+        if regex.span.is_empty() {
+            regex.span = self.span();
         }
+        Ok(regex)
     }
 
     fn validate(&self, options: &CompileOptions) -> Result<(), CompileError> {
@@ -22,6 +22,22 @@
 //! };
 //! ```
 //!
+//! To get the errors and warnings separately, as well as more information
+//! about the regex, use [`Expr::parse_and_compile_detailed`]:
+//!
+//! ```
+//! use pomsky::Expr;
+//! use pomsky::options::CompileOptions;
+//!
+//! match Expr::parse_and_compile_detailed(":name('test')", CompileOptions::default()) {
+//!     Ok(compiled) => {
+//!         assert_eq!(compiled.output.regex, "(?P<name>test)");
+//!         assert_eq!(compiled.output.groups[0].name.as_deref(), Some("name"));
+//!     }
+//!     Err(failure) => eprintln!("{failure}"),
+//! }
+//! ```
+//!
 //! You can get fancy error messages with [miette] by enabling the `diagnostics`
 //! feature:
 //!
@@ -55,10 +71,14 @@ pub mod diagnose;
 pub mod error;
 pub mod features;
 pub mod options;
+pub mod output;
+pub mod source_map;
 
 mod compile;
 mod exprs;
+mod literal;
 mod regex;
 
 pub use exprs::Expr;
 pub use pomsky_syntax::diagnose::{ParseError, ParseWarning as Warning};
+pub use pomsky_syntax::Span;
@@ -0,0 +1,193 @@
+//! Formatting a compiled regex as a string or regex literal of the language
+//! that is typically used with the regex flavor.
+
+use crate::{options::RegexFlavor, output::RegexFlags};
+
+pub(crate) fn to_literal(regex: &str, flavor: RegexFlavor, flags: &RegexFlags) -> String {
+    match flavor {
+        // Java and Emacs Lisp
+        RegexFlavor::Java | RegexFlavor::Emacs => quoted(regex),
+        RegexFlavor::JavaScript => {
+            let regex = if regex.is_empty() { "(?:)" } else { regex };
+            let mut literal = String::from("/");
+            escape_delimiter(regex, '/', &mut literal);
+            literal.push('/');
+            literal.push_str(&flags.required);
+            literal.push_str(&flags.recommended);
+            literal
+        }
+        RegexFlavor::Python | RegexFlavor::PythonRegex => python_raw(regex),
+        // C# verbatim string
+        RegexFlavor::DotNet => format!("@\"{}\"", regex.replace('"', "\"\"")),
+        RegexFlavor::Ruby => ruby_literal(regex),
+        RegexFlavor::Pcre => php_literal(regex),
+        RegexFlavor::Rust => {
+            let hashes = "#".repeat(required_hashes(regex, "\""));
+            format!("r{hashes}\"{regex}\"{hashes}")
+        }
+        // Go raw string
+        RegexFlavor::RE2 if !regex.contains(['`', '\r']) => format!("`{regex}`"),
+        RegexFlavor::RE2 => quoted(regex),
+        // Swift raw string, since ICU is used by `NSRegularExpression`
+        RegexFlavor::Icu if !regex.contains(['\n', '\r']) => {
+            let hashes = "#".repeat(required_hashes(regex, "\"").max(required_hashes(regex, "\\")));
+            format!("{hashes}\"{regex}\"{hashes}")
+        }
+        RegexFlavor::Icu => quoted(regex),
+        // POSIX shell
+        RegexFlavor::PosixExtended => format!("'{}'", regex.replace('\'', r"'\''")),
+        RegexFlavor::Vim if !regex.contains(['\n', '\r']) => {
+            format!("'{}'", regex.replace('\'', "''"))
+        }
+        RegexFlavor::Vim => quoted(regex),
+    }
+}
+
+/// A double-quoted string with C-like escapes
+fn quoted(regex: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in regex.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            _ => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Escapes the delimiter of a regex literal, unless it is already escaped.
+/// Line breaks are escaped as well, since regex literals can't contain them.
+fn escape_delimiter(regex: &str, delimiter: char, buf: &mut String) {
+    let mut chars = regex.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                buf.push('\\');
+                match chars.next() {
+                    Some('\n') => buf.push('n'),
+                    Some('\r') => buf.push('r'),
+                    Some(c) => buf.push(c),
+                    None => {}
+                }
+            }
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            _ if c == delimiter => {
+                buf.push('\\');
+                buf.push(c);
+            }
+            _ => buf.push(c),
+        }
+    }
+}
+
+/// Returns the number of `#` needed so that the raw string isn't terminated
+/// early, i.e. the regex doesn't contain `delimiter` followed by that many `#`
+fn required_hashes(regex: &str, delimiter: &str) -> usize {
+    let mut hashes = 0;
+    for (i, _) in regex.match_indices(delimiter) {
+        let count = regex[i + delimiter.len()..].chars().take_while(|&c| c == '#').count();
+        hashes = hashes.max(count + 1);
+    }
+    hashes
+}
+
+/// A raw string, e.g. `r"\d+"`. If the regex contains all kinds of quotes, a
+/// normal string is used instead.
+fn python_raw(regex: &str) -> String {
+    // a raw string can't end with an odd number of backslashes
+    let trailing_backslashes = regex.chars().rev().take_while(|&c| c == '\\').count();
+    if trailing_backslashes % 2 == 0 {
+        let has_newline = regex.contains(['\n', '\r']);
+        for quote in ["\"", "'", "\"\"\"", "'''"] {
+            // only triple-quoted strings can span multiple lines, but they
+            // can't end with their quote character
+            let fits = match quote.len() {
+                3 => !regex.ends_with(&quote[..1]),
+                _ => !has_newline,
+            };
+            if fits && !regex.contains(quote) {
+                return format!("r{quote}{regex}{quote}");
+            }
+        }
+    }
+    quoted(regex)
+}
+
+/// A `%r{...}` literal. Braces are escaped in character classes, where they
+/// may be unbalanced, and `#` is escaped where it would start an interpolation.
+fn ruby_literal(regex: &str) -> String {
+    let mut literal = String::from("%r{");
+    let mut class_depth = 0usize;
+    let mut chars = regex.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                literal.push('\\');
+                if let Some(c) = chars.next() {
+                    literal.push(c);
+                }
+                continue;
+            }
+            '[' => class_depth += 1,
+            ']' => class_depth = class_depth.saturating_sub(1),
+            '{' | '}' if class_depth > 0 => literal.push('\\'),
+            '#' if matches!(chars.peek(), Some('{' | '$' | '@')) => literal.push('\\'),
+            _ => {}
+        }
+        literal.push(c);
+    }
+    literal.push('}');
+    literal
+}
+
+/// A single-quoted PHP string containing a PCRE pattern with `/` delimiters
+/// and the `u` modifier, e.g. `'/\d+/u'`
+fn php_literal(regex: &str) -> String {
+    let mut pattern = String::new();
+    escape_delimiter(regex, '/', &mut pattern);
+
+    // in single-quoted strings, only `\\` and `\'` are escape sequences
+    let mut literal = String::from("'/");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if matches!(chars.peek(), Some('\\' | '\'')) => literal.push_str("\\\\"),
+            '\'' => literal.push_str("\\'"),
+            _ => literal.push(c),
+        }
+    }
+    literal.push_str("/u'");
+    literal
+}
+
+#[test]
+fn test_literals() {
+    let literal = |regex: &str, flavor| to_literal(regex, flavor, &RegexFlags::default());
+
+    assert_eq!(literal(r#"\d"'"#, RegexFlavor::Java), r#""\\d\"'""#);
+    assert_eq!(literal(r"a/[/]\/", RegexFlavor::JavaScript), r"/a\/[\/]\//");
+    assert_eq!(literal("", RegexFlavor::JavaScript), "/(?:)/");
+    let flags = RegexFlags { required: "u".into(), recommended: String::new() };
+    assert_eq!(to_literal(r"\p{L}", RegexFlavor::JavaScript, &flags), r"/\p{L}/u");
+
+    assert_eq!(literal(r"\d", RegexFlavor::Python), r#"r"\d""#);
+    assert_eq!(literal(r#"\d""#, RegexFlavor::Python), r#"r'\d"'"#);
+    assert_eq!(literal(r#"'""#, RegexFlavor::Python), r#"r''''"'''"#);
+    assert_eq!(literal("a'\"b", RegexFlavor::Python), "r\"\"\"a'\"b\"\"\"");
+
+    assert_eq!(literal(r#"\d"a""#, RegexFlavor::DotNet), r#"@"\d""a""""#);
+    assert_eq!(literal(r"#{2}[{}]\{a{2}", RegexFlavor::Ruby), r"%r{\#{2}[\{\}]\{a{2}}");
+    assert_eq!(literal(r"'\\\d/", RegexFlavor::Pcre), r"'/\'\\\\\d\//u'");
+    assert_eq!(literal(r##"\d"#"##, RegexFlavor::Rust), r###"r##"\d"#"##"###);
+    assert_eq!(literal(r"\d", RegexFlavor::Rust), r#"r"\d""#);
+    assert_eq!(literal(r"\d`", RegexFlavor::RE2), r#""\\d`""#);
+    assert_eq!(literal(r#"\d""#, RegexFlavor::Icu), r##"#"\d""#"##);
+    assert_eq!(literal(r"a'b", RegexFlavor::PosixExtended), r"'a'\''b'");
+    assert_eq!(literal(r"a'b", RegexFlavor::Vim), "'a''b'");
+}
@@ -1,6 +1,13 @@
 //! Contains parser and compiler options passed to pomsky.
 
-use crate::features::PomskyFeatures;
+use std::str::FromStr;
+
+use pomsky_syntax::Span;
+
+use crate::{
+    diagnose::{CompileError, CompileErrorKind, Feature, Support},
+    features::PomskyFeatures,
+};
 
 /// Options passed to the pomsky compiler
 #[derive(Debug, Clone, Copy)]
@@ -19,6 +26,35 @@ pub struct CompileOptions {
 
     /// Allowed pomsky features. By default, all features are allowed.
     pub allowed_features: PomskyFeatures,
+
+    /// How the emitted regex is formatted. Defaults to
+    /// [`OutputStyle::Minimal`].
+    pub output_style: OutputStyle,
+
+    /// The version of the targeted regex engine, if known. Compilation fails
+    /// if it doesn't belong to the targeted flavor.
+    ///
+    /// If it is `None`, pomsky forbids features that are missing in older
+    /// versions, or warns about them.
+    pub flavor_version: Option<FlavorVersion>,
+
+    /// Whether `Grapheme` is emulated with Unicode properties in JavaScript
+    /// and Rust, which don't support `\X`. Defaults to `false`.
+    ///
+    /// The emulation is an approximation of an extended grapheme cluster. It
+    /// handles CR LF, control characters, combining marks, emoji ZWJ
+    /// sequences, regional indicator pairs and Hangul syllables, but not
+    /// prepended characters.
+    pub emulate_grapheme: bool,
+
+    /// Whether atomic groups are emulated in JavaScript, which doesn't support
+    /// them. Defaults to `false`.
+    ///
+    /// An atomic group `atomic(...)` is emulated with a lookahead, which is
+    /// atomic, and a backreference: `(?=(...))\1`. This adds a capturing group
+    /// to the regex, so the numbers of the following groups are shifted. This
+    /// doesn't work within lookbehind.
+    pub emulate_atomic_groups: bool,
 }
 
 impl Default for CompileOptions {
@@ -27,8 +63,70 @@ impl Default for CompileOptions {
             flavor: RegexFlavor::default(),
             max_range_size: 6,
             allowed_features: PomskyFeatures::default(),
+            output_style: OutputStyle::default(),
+            flavor_version: None,
+            emulate_grapheme: false,
+            emulate_atomic_groups: false,
+        }
+    }
+}
+
+impl CompileOptions {
+    /// Returns the targeted Python version, if the flavor is Python and the
+    /// version is known.
+    pub(crate) fn python_version(&self) -> Option<(u8, u8)> {
+        match (self.flavor, self.flavor_version) {
+            (RegexFlavor::Python, Some(FlavorVersion::Python(major, minor))) => {
+                Some((major, minor))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the targeted ECMAScript version, if the flavor is JavaScript
+    /// and the version is known.
+    pub(crate) fn ecma_version(&self) -> Option<EcmaVersion> {
+        match (self.flavor, self.flavor_version) {
+            (RegexFlavor::JavaScript, Some(FlavorVersion::JavaScript(version))) => Some(version),
+            _ => None,
+        }
+    }
+
+    /// Returns whether a regex feature is supported. Unlike
+    /// [`RegexFlavor::supports`], this takes the flavor version and the enabled
+    /// emulations into account.
+    pub fn supports(&self, feature: Feature) -> Support {
+        let before_es2018 = matches!(self.ecma_version(), Some(v) if v < EcmaVersion::Es2018);
+        // Python supports atomic groups since 3.11, but it's fairly new, so we forbid
+        // it unless a newer version is specified
+        let python_3_11 = matches!(self.python_version(), Some(v) if v >= (3, 11));
+
+        match (feature, self.flavor.supports(feature)) {
+            (Feature::Grapheme, Support::Emulated) if !self.emulate_grapheme => Support::No,
+            (Feature::AtomicGroups, Support::Emulated) if !self.emulate_atomic_groups => {
+                Support::No
+            }
+            (Feature::AtomicGroups, _) if python_3_11 => Support::Yes,
+            // Unicode properties are also used to emulate `[w]`, `[d]` and `Grapheme`
+            (
+                Feature::Lookbehind
+                | Feature::NamedCaptureGroups
+                | Feature::UnicodeProp
+                | Feature::UnicodeBinaryProp
+                | Feature::Grapheme,
+                _,
+            ) if before_es2018 => Support::No,
+            // we only warn if we don't know whether the runtime supports it
+            (Feature::Lookbehind, Support::Warn) if self.ecma_version().is_some() => Support::Yes,
+            (_, support) => support,
         }
     }
+
+    /// Returns an error if a regex feature is not supported, otherwise how it
+    /// is supported
+    pub(crate) fn require(&self, feature: Feature, span: Span) -> Result<Support, CompileError> {
+        self.flavor.check(feature, self.supports(feature), span)
+    }
 }
 
 /// A regex flavor is a regex engine or a set of regex engines that are similar
@@ -41,6 +139,10 @@ pub enum RegexFlavor {
     Pcre,
     /// Python's `re` module
     Python,
+    /// The third-party [`regex`](https://pypi.org/project/regex/) module for
+    /// Python. Unlike `re`, it supports Unicode properties, `\X`, atomic
+    /// groups and lookbehind of any length.
+    PythonRegex,
     /// The `java.util.regex.Pattern` class
     Java,
     /// JavaScript (ECMAScript) built-in regular expressions
@@ -51,6 +153,157 @@ pub enum RegexFlavor {
     Ruby,
     /// The Rust `regex` crate
     Rust,
+    /// RE2 and engines with the same syntax, such as Go's `regexp` package.
+    ///
+    /// RE2's shorthands only match ASCII characters, so `[w]`, `[d]` and `[s]`
+    /// are emulated with Unicode properties, and word boundaries are not
+    /// supported.
+    RE2,
+    /// POSIX Extended Regular Expressions (ERE), as used by `grep -E`, `awk`
+    /// and `sed -E`.
+    ///
+    /// ERE has no non-capturing groups, so all groups are capturing, and group
+    /// numbers in the emitted regex may be higher than in pomsky. Note that
+    /// the dot (`.`) also matches line breaks in ERE, but most tools that use
+    /// ERE match line by line.
+    PosixExtended,
+    /// ICU regular expressions, used by Swift's `NSRegularExpression`,
+    /// MySQL 8 and R's `stringi` package
+    Icu,
+    /// Vim's regular expressions. The regex starts with `\v` to enable "very
+    /// magic" mode.
+    ///
+    /// Vim has no named groups, so they are emitted as unnamed groups.
+    /// Shorthands are emulated with POSIX classes, e.g. `[w]` = `[[:alnum:]_]`.
+    /// `Start` and `End` match at the start and end of the file or string, not
+    /// of the line.
+    Vim,
+    /// Emacs Lisp regular expressions, as used by `re-search-forward` and
+    /// `string-match`.
+    ///
+    /// Emacs has no named groups, so they are emitted as unnamed groups.
+    /// Shorthands are emulated with POSIX classes, e.g. `[w]` = `[[:alnum:]_]`.
+    /// `Start` and `End` match at the start and end of the buffer or string,
+    /// not of the line. Note that the regex is not escaped as an Emacs Lisp
+    /// string literal.
+    Emacs,
+}
+
+impl RegexFlavor {
+    /// All supported regex flavors
+    pub const ALL: &'static [RegexFlavor] = &[
+        RegexFlavor::Pcre,
+        RegexFlavor::Python,
+        RegexFlavor::PythonRegex,
+        RegexFlavor::Java,
+        RegexFlavor::JavaScript,
+        RegexFlavor::DotNet,
+        RegexFlavor::Ruby,
+        RegexFlavor::Rust,
+        RegexFlavor::RE2,
+        RegexFlavor::PosixExtended,
+        RegexFlavor::Icu,
+        RegexFlavor::Vim,
+        RegexFlavor::Emacs,
+    ];
+}
+
+impl RegexFlavor {
+    /// Returns whether a regex feature is supported in this flavor.
+    ///
+    /// This doesn't take the [`CompileOptions`] into account. For example,
+    /// Python supports atomic groups since version 3.11, but `No` is returned
+    /// for [`RegexFlavor::Python`] since the version is unknown. And
+    /// [`Support::Emulated`] features in JavaScript and Rust are only emulated
+    /// when enabled in the options.
+    pub fn supports(self, feature: Feature) -> Support {
+        use RegexFlavor::*;
+
+        let supported = |yes: bool| if yes { Support::Yes } else { Support::No };
+
+        match feature {
+            Feature::NamedCaptureGroups => match self {
+                // named groups are emitted as unnamed groups
+                PosixExtended | Vim | Emacs => Support::Emulated,
+                _ => Support::Yes,
+            },
+            Feature::AtomicGroups => match self {
+                JavaScript => Support::Emulated,
+                Python | Rust | RE2 | PosixExtended | Emacs => Support::No,
+                _ => Support::Yes,
+            },
+            Feature::Lookaround => supported(!matches!(self, Rust | RE2 | PosixExtended | Emacs)),
+            Feature::Lookbehind => match self {
+                // not supported in all browsers
+                JavaScript => Support::Warn,
+                Rust | RE2 | PosixExtended | Emacs => Support::No,
+                _ => Support::Yes,
+            },
+            Feature::UnboundedLookbehind => {
+                supported(matches!(self, JavaScript | DotNet | PythonRegex | Vim))
+            }
+            Feature::Grapheme => match self {
+                Pcre | PythonRegex | Java | Ruby | Icu => Support::Yes,
+                JavaScript | Rust => Support::Emulated,
+                _ => Support::No,
+            },
+            Feature::UnicodeBlock => {
+                supported(matches!(self, DotNet | Java | Ruby | Icu | PythonRegex))
+            }
+            Feature::UnicodeProp => {
+                supported(!matches!(self, Python | PosixExtended | Vim | Emacs))
+            }
+            Feature::UnicodeBinaryProp => {
+                supported(matches!(self, JavaScript | Rust | Icu | PythonRegex))
+            }
+            Feature::Backreference | Feature::MixedReferences if matches!(self, Rust | RE2) => {
+                Support::No
+            }
+            Feature::Backreference => Support::Yes,
+            Feature::MixedReferences => supported(self != Ruby),
+            Feature::ForwardReference => supported(matches!(self, Pcre | Java | DotNet | Ruby)),
+            // relative references are converted to absolute references
+            Feature::RelativeReference => match self.supports(Feature::Backreference) {
+                Support::Yes => Support::Emulated,
+                support => support,
+            },
+            Feature::NonNegativeRelativeReference => {
+                match self.supports(Feature::ForwardReference) {
+                    Support::Yes => Support::Emulated,
+                    support => support,
+                }
+            }
+            Feature::NegativeShorthandW => {
+                supported(!matches!(self, JavaScript | RE2 | PosixExtended | Vim | Emacs))
+            }
+            Feature::RepeatedAssertion => supported(self != Ruby),
+            Feature::FreeSpacing => {
+                supported(!matches!(self, JavaScript | RE2 | PosixExtended | Vim | Emacs))
+            }
+            Feature::LazyQuantifiers => supported(self != PosixExtended),
+            // `\b` isn't Unicode-aware in RE2
+            Feature::WordBoundaries => supported(!matches!(self, RE2 | PosixExtended)),
+            Feature::LazyBoundedRepetition => supported(!matches!(self, PosixExtended | Emacs)),
+            Feature::NegativeShorthandInClass => {
+                supported(!matches!(self, RE2 | PosixExtended | Vim | Emacs))
+            }
+            Feature::BackreferenceAbove9 => {
+                supported(!matches!(self, Rust | RE2 | PosixExtended | Vim | Emacs))
+            }
+        }
+    }
+
+    fn check(
+        self,
+        feature: Feature,
+        support: Support,
+        span: Span,
+    ) -> Result<Support, CompileError> {
+        match support {
+            Support::No => Err(CompileErrorKind::Unsupported(feature, self).at(span)),
+            support => Ok(support),
+        }
+    }
 }
 
 impl Default for RegexFlavor {
@@ -58,3 +311,146 @@ impl Default for RegexFlavor {
         RegexFlavor::Pcre
     }
 }
+
+/// Parses a lowercase flavor name such as `python` or `js`, as accepted by the
+/// CLI
+impl FromStr for RegexFlavor {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "pcre" => RegexFlavor::Pcre,
+            "python" => RegexFlavor::Python,
+            "python-regex" => RegexFlavor::PythonRegex,
+            "java" => RegexFlavor::Java,
+            "js" | "javascript" => RegexFlavor::JavaScript,
+            "dotnet" | ".net" => RegexFlavor::DotNet,
+            "ruby" => RegexFlavor::Ruby,
+            "rust" => RegexFlavor::Rust,
+            "re2" | "go" => RegexFlavor::RE2,
+            "posix-extended" | "ere" => RegexFlavor::PosixExtended,
+            "icu" => RegexFlavor::Icu,
+            "vim" => RegexFlavor::Vim,
+            "emacs" => RegexFlavor::Emacs,
+            _ => return Err(()),
+        })
+    }
+}
+
+/// The version of a regex flavor, which determines the supported features.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum FlavorVersion {
+    /// The major and minor version of Python, e.g. `Python(3, 11)`. Python
+    /// supports atomic groups since 3.11.
+    Python(u8, u8),
+    /// The ECMAScript version supported by the JavaScript runtime
+    JavaScript(EcmaVersion),
+}
+
+impl FlavorVersion {
+    /// Parses the version of a regex flavor, e.g. `3.11` for Python or
+    /// `es2018` for JavaScript. ECMAScript versions are given as `es` followed
+    /// by the year or by `6`.
+    ///
+    /// Returns `None` if the version is invalid, or if the flavor has no
+    /// versions.
+    pub fn parse(flavor: RegexFlavor, version: &str) -> Option<FlavorVersion> {
+        match flavor {
+            RegexFlavor::JavaScript => match version.strip_prefix("es")?.parse::<u16>().ok()? {
+                6 | 2015..=2017 => Some(FlavorVersion::JavaScript(EcmaVersion::Es2015)),
+                2018.. => Some(FlavorVersion::JavaScript(EcmaVersion::Es2018)),
+                _ => None,
+            },
+            RegexFlavor::Python => {
+                let (major, minor) = version.split_once('.')?;
+                Some(FlavorVersion::Python(major.parse().ok()?, minor.parse().ok()?))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the regex flavor this version belongs to
+    pub fn flavor(self) -> RegexFlavor {
+        match self {
+            FlavorVersion::Python(..) => RegexFlavor::Python,
+            FlavorVersion::JavaScript(_) => RegexFlavor::JavaScript,
+        }
+    }
+}
+
+/// An ECMAScript version. Only versions that added regex features are listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum EcmaVersion {
+    /// ECMAScript 2015, which added the `u` flag
+    Es2015,
+    /// ECMAScript 2018, which added lookbehind and named capturing groups
+    Es2018,
+}
+
+/// The formatting of the emitted regex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum OutputStyle {
+    /// A compact regex without any insignificant whitespace
+    #[default]
+    Minimal,
+    /// A regex in free-spacing mode (the `x` flag), with groups and
+    /// alternations spread across multiple indented lines. Comments in the
+    /// pomsky expression are emitted before the rules that follow them.
+    ///
+    /// This is not supported in JavaScript, RE2, POSIX ERE, Vim and Emacs,
+    /// which have no `x` flag.
+    Readable,
+}
+
+#[test]
+fn test_supports() {
+    assert_eq!(RegexFlavor::Pcre.supports(Feature::AtomicGroups), Support::Yes);
+    assert_eq!(RegexFlavor::Rust.supports(Feature::Lookaround), Support::No);
+    assert_eq!(RegexFlavor::JavaScript.supports(Feature::Lookbehind), Support::Warn);
+    assert_eq!(RegexFlavor::JavaScript.supports(Feature::Grapheme), Support::Emulated);
+    assert_eq!(RegexFlavor::Vim.supports(Feature::NamedCaptureGroups), Support::Emulated);
+
+    let options =
+        |flavor, flavor_version| CompileOptions { flavor, flavor_version, ..Default::default() };
+    let python =
+        |major, minor| options(RegexFlavor::Python, Some(FlavorVersion::Python(major, minor)));
+    let js = |version| options(RegexFlavor::JavaScript, Some(FlavorVersion::JavaScript(version)));
+
+    assert_eq!(options(RegexFlavor::Python, None).supports(Feature::AtomicGroups), Support::No);
+    assert_eq!(python(3, 10).supports(Feature::AtomicGroups), Support::No);
+    assert_eq!(python(3, 11).supports(Feature::AtomicGroups), Support::Yes);
+    assert_eq!(js(EcmaVersion::Es2015).supports(Feature::Lookbehind), Support::No);
+    assert_eq!(js(EcmaVersion::Es2018).supports(Feature::Lookbehind), Support::Yes);
+    assert_eq!(js(EcmaVersion::Es2015).supports(Feature::UnicodeProp), Support::No);
+    assert_eq!(js(EcmaVersion::Es2018).supports(Feature::UnicodeProp), Support::Yes);
+    assert_eq!(options(RegexFlavor::JavaScript, None).supports(Feature::Grapheme), Support::No);
+
+    let emulated = CompileOptions {
+        flavor: RegexFlavor::JavaScript,
+        emulate_grapheme: true,
+        ..Default::default()
+    };
+    assert_eq!(emulated.supports(Feature::Grapheme), Support::Emulated);
+    assert_eq!(emulated.supports(Feature::AtomicGroups), Support::No);
+}
+
+#[test]
+fn test_parse_version() {
+    use RegexFlavor::{JavaScript, Python, Rust};
+    let js = |version| Some(FlavorVersion::JavaScript(version));
+
+    assert_eq!("js".parse(), Ok(JavaScript));
+    assert_eq!("Python".parse::<RegexFlavor>(), Err(()));
+    assert_eq!(FlavorVersion::parse(JavaScript, "es6"), js(EcmaVersion::Es2015));
+    assert_eq!(FlavorVersion::parse(JavaScript, "es2017"), js(EcmaVersion::Es2015));
+    assert_eq!(FlavorVersion::parse(JavaScript, "es2022"), js(EcmaVersion::Es2018));
+    assert_eq!(FlavorVersion::parse(JavaScript, "es5"), None);
+    assert_eq!(FlavorVersion::parse(Python, "3.11"), Some(FlavorVersion::Python(3, 11)));
+    assert_eq!(FlavorVersion::parse(Python, "3"), None);
+    assert_eq!(FlavorVersion::parse(Rust, "1.0"), None);
+}
@@ -0,0 +1,258 @@
+//! Contains the types returned by
+//! [`Expr::parse_and_compile_detailed`](crate::Expr::parse_and_compile_detailed)
+//! when compilation succeeds.
+
+use std::time::Duration;
+
+use pomsky_syntax::Span;
+
+use crate::{
+    diagnose::{Diagnostic, Feature},
+    error::CompileFailure,
+    options::RegexFlavor,
+    regex::UnicodeUsage,
+};
+
+/// A successfully compiled pomsky expression
+#[derive(Debug, Clone)]
+pub struct Compiled {
+    /// The regex, and information about it
+    pub output: CompileOutput,
+    /// The warnings emitted while parsing and compiling the expression
+    pub warnings: Vec<Diagnostic>,
+    /// The time spent in each phase
+    pub timings: Timings,
+}
+
+/// The result of compiling an expression for one regex flavor, as returned by
+/// [`Expr::check_all_flavors`](crate::Expr::check_all_flavors)
+#[derive(Debug, Clone)]
+pub struct FlavorCheck {
+    /// The regex flavor the expression was compiled for
+    pub flavor: RegexFlavor,
+    /// The compiled regex, or the errors that prevented compilation
+    pub result: Result<Compiled, CompileFailure>,
+    /// The regex feature that isn't supported by this flavor, if that is why
+    /// compilation failed
+    pub unsupported_feature: Option<Feature>,
+}
+
+/// The result of a successful compilation: The regex, and information about
+/// it that is otherwise difficult to obtain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileOutput {
+    /// The compiled regex
+    pub regex: String,
+    /// The capturing groups in the regex, ordered by their index
+    pub groups: Vec<GroupInfo>,
+    /// The flags that should be passed to the regex engine
+    pub flags: RegexFlags,
+}
+
+impl CompileOutput {
+    /// Returns the regex as a literal that can be pasted into source code of
+    /// the language that is typically used with the flavor. Quotes and
+    /// delimiters in the regex are escaped as needed.
+    ///
+    /// | Flavor     | Literal                                |
+    /// |------------|----------------------------------------|
+    /// | Java       | string literal, `"\\d+"`               |
+    /// | JavaScript | regex literal with flags, `/\d+/u`     |
+    /// | Python     | raw string, `r"\d+"`                   |
+    /// | .NET       | C# verbatim string, `@"\d+"`           |
+    /// | Ruby       | `%r{\d+}`                              |
+    /// | PCRE       | PHP string with delimiters, `'/\d+/u'` |
+    /// | Rust       | raw string, `r"\d+"`                   |
+    /// | RE2        | Go raw string, `` `\d+` ``             |
+    /// | ICU        | Swift raw string, `#"\d+"#`            |
+    /// | POSIX ERE  | single-quoted shell string, `'a+'`     |
+    /// | Vim        | single-quoted string, `'a+'`           |
+    /// | Emacs      | Emacs Lisp string, `"a+"`              |
+    pub fn to_literal(&self, flavor: RegexFlavor) -> String {
+        crate::literal::to_literal(&self.regex, flavor, &self.flags)
+    }
+}
+
+/// Flags that must or should be enabled when compiling the regex with the
+/// regex engine. Each flag is a letter as used by the targeted flavor, e.g.
+/// `u` for JavaScript's `new RegExp(regex, "u")`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegexFlags {
+    /// Flags without which the regex is invalid, or matches incorrectly
+    pub required: String,
+    /// Flags that make the regex match as intended, e.g. Unicode-aware. They
+    /// don't include the required flags.
+    pub recommended: String,
+}
+
+impl RegexFlags {
+    pub(crate) fn new(flavor: RegexFlavor, unicode: UnicodeUsage) -> Self {
+        let UnicodeUsage { properties, braced_escapes, shorthands, code_points } = unicode;
+        let (required, recommended) = match flavor {
+            // `\p{...}` and `\u{...}` are syntax errors without the `u` flag, and
+            // without it, characters outside the BMP are matched as two code units
+            RegexFlavor::JavaScript => (properties || braced_escapes, code_points),
+            // code points above 0xFF can't be matched without UTF mode, and
+            // shorthands and properties only match ASCII without UCP
+            RegexFlavor::Pcre => (braced_escapes, properties || shorthands || code_points),
+            // `re.UNICODE`; the default for `str` patterns, but not for `bytes`
+            RegexFlavor::Python | RegexFlavor::PythonRegex => (false, shorthands || code_points),
+            // `UNICODE_CHARACTER_CLASS`; shorthands only match ASCII otherwise
+            RegexFlavor::Java => {
+                let flag = if shorthands { "U" } else { "" };
+                return RegexFlags { required: String::new(), recommended: flag.into() };
+            }
+            RegexFlavor::DotNet
+            | RegexFlavor::Ruby
+            | RegexFlavor::Rust
+            | RegexFlavor::RE2
+            | RegexFlavor::PosixExtended
+            | RegexFlavor::Icu
+            | RegexFlavor::Vim
+            | RegexFlavor::Emacs => (false, false),
+        };
+
+        let flag = |enabled: bool| if enabled { "u".to_string() } else { String::new() };
+        RegexFlags { required: flag(required), recommended: flag(recommended && !required) }
+    }
+}
+
+/// Information about a capturing group in the compiled regex
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupInfo {
+    /// The index of the group. The first group has index 1.
+    pub index: u32,
+    /// The name of the group, if it is a named group
+    pub name: Option<String>,
+    /// The span of the group in the pomsky expression
+    pub span: Span,
+}
+
+/// The time spent in each phase of the compilation.
+///
+/// Time is not measured on `wasm32-unknown-unknown`, which has no clock, so
+/// all durations are zero there.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    /// Parsing the expression. This is zero if it was parsed beforehand.
+    pub parse: Duration,
+    /// Validating, compiling and optimizing the expression
+    pub compile: Duration,
+    /// Generating the regex string
+    pub codegen: Duration,
+}
+
+impl Timings {
+    /// Returns the total time spent
+    pub fn total(&self) -> Duration {
+        self.parse + self.compile + self.codegen
+    }
+}
+
+/// Measures elapsed time, except on platforms where `std::time::Instant`
+/// panics.
+pub(crate) struct Stopwatch {
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    start: std::time::Instant,
+}
+
+impl Stopwatch {
+    pub(crate) fn start() -> Self {
+        Stopwatch {
+            #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        return self.start.elapsed();
+
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        return Duration::ZERO;
+    }
+}
+
+#[test]
+fn test_groups() {
+    use crate::{options::CompileOptions, Expr};
+
+    let compiled =
+        Expr::parse_and_compile_detailed(":('a') :name('b' :('c'))", CompileOptions::default());
+    let output = compiled.unwrap().output;
+    assert_eq!(output.regex, "(a)(?P<name>b(c))");
+    assert_eq!(output.flags, RegexFlags::default());
+    assert_eq!(
+        output.groups,
+        [
+            GroupInfo { index: 1, name: None, span: Span::new(0, 6) },
+            GroupInfo { index: 2, name: Some("name".into()), span: Span::new(7, 24) },
+            GroupInfo { index: 3, name: None, span: Span::new(17, 23) },
+        ]
+    );
+
+    // POSIX ERE has no non-capturing groups, so the numbers change
+    let options = CompileOptions { flavor: RegexFlavor::PosixExtended, ..Default::default() };
+    let output = Expr::parse_and_compile_detailed("('a' | 'b') :x('c')", options).unwrap().output;
+    assert_eq!(output.regex, "(a|b)(c)");
+    assert_eq!(
+        output.groups,
+        [GroupInfo { index: 2, name: Some("x".into()), span: Span::new(12, 19) }]
+    );
+}
+
+#[test]
+fn test_flags() {
+    use crate::{options::CompileOptions, Expr};
+
+    let flags = |input, flavor| {
+        let options = CompileOptions { flavor, ..Default::default() };
+        let flags = Expr::parse_and_compile_detailed(input, options).unwrap().output.flags;
+        (flags.required, flags.recommended)
+    };
+    let js = RegexFlavor::JavaScript;
+
+    assert_eq!(flags("'a'", js), ("".into(), "".into()));
+    assert_eq!(flags("'ä'", js), ("".into(), "u".into()));
+    assert_eq!(flags("[Greek]", js), ("u".into(), "".into()));
+    assert_eq!(flags("[word]", js), ("u".into(), "".into()));
+    assert_eq!(flags("U+1F600", js), ("u".into(), "".into()));
+    assert_eq!(flags("U+1F600", RegexFlavor::Pcre), ("u".into(), "".into()));
+    assert_eq!(flags("[word]", RegexFlavor::Pcre), ("".into(), "u".into()));
+    assert_eq!(flags("[word]", RegexFlavor::Java), ("".into(), "U".into()));
+    assert_eq!(flags("[word]", RegexFlavor::Rust), ("".into(), "".into()));
+}
+
+#[test]
+fn test_check_all_flavors() {
+    use crate::{options::CompileOptions, Expr};
+
+    let input = "atomic('a'+) (<< 'b')";
+    let (parsed, _) = Expr::parse(input);
+    let checks = parsed.unwrap().check_all_flavors(input, CompileOptions::default());
+    assert_eq!(checks.len(), RegexFlavor::ALL.len());
+
+    let supported = checks
+        .iter()
+        .filter(|check| check.result.is_ok())
+        .map(|check| check.flavor)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        supported,
+        [
+            RegexFlavor::Pcre,
+            RegexFlavor::PythonRegex,
+            RegexFlavor::Java,
+            RegexFlavor::DotNet,
+            RegexFlavor::Ruby,
+            RegexFlavor::Icu,
+            RegexFlavor::Vim,
+        ]
+    );
+
+    let check = |flavor| checks.iter().find(|check| check.flavor == flavor).unwrap();
+    assert_eq!(check(RegexFlavor::JavaScript).unsupported_feature, Some(Feature::AtomicGroups));
+    assert_eq!(check(RegexFlavor::Python).unsupported_feature, Some(Feature::AtomicGroups));
+    assert_eq!(check(RegexFlavor::Emacs).unsupported_feature, Some(Feature::AtomicGroups));
+    assert_eq!(check(RegexFlavor::Pcre).unsupported_feature, None);
+}
@@ -1,8 +1,16 @@
-use std::borrow::{Borrow, Cow};
+use std::{
+    borrow::{Borrow, Cow},
+    collections::VecDeque,
+    ops::Range,
+};
 
-use pomsky_syntax::exprs::{BoundaryKind, Category, CodeBlock, OtherProperties, Script};
+use pomsky_syntax::{
+    exprs::{BoundaryKind, Category, CodeBlock, OtherProperties, Script},
+    Span,
+};
 
 use crate::{
+    diagnose::CompileError,
     exprs::{
         alternation::RegexAlternation,
         boundary::boundary_kind_codegen,
@@ -13,15 +21,27 @@ use crate::{
         reference::RegexReference,
         repetition::RegexRepetition,
     },
-    options::RegexFlavor,
+    options::{OutputStyle, RegexFlavor},
+    source_map::SourceMapEntry,
 };
 
 mod optimize;
 
 pub(super) use optimize::Count;
 
+/// A node in the regex syntax tree, together with the span of the pomsky
+/// expression it was compiled from.
 #[cfg_attr(feature = "dbg", derive(Debug))]
-pub(crate) enum Regex<'i> {
+pub(crate) struct Regex<'i> {
+    pub(crate) kind: RegexKind<'i>,
+    /// The span of the pomsky rule this was compiled from. This is empty for
+    /// nodes that don't correspond to a rule of their own, and for rules
+    /// without a span, such as the dot.
+    pub(crate) span: Span,
+}
+
+#[cfg_attr(feature = "dbg", derive(Debug))]
+pub(crate) enum RegexKind<'i> {
     /// A literal string
     Literal(Cow<'i, str>),
     /// A regex string that is inserted verbatim into the output
@@ -57,7 +77,13 @@ pub(crate) enum Regex<'i> {
 
 impl Default for Regex<'_> {
     fn default() -> Self {
-        Regex::Literal("".into())
+        RegexKind::Literal("".into()).into()
+    }
+}
+
+impl<'i> From<RegexKind<'i>> for Regex<'i> {
+    fn from(kind: RegexKind<'i>) -> Self {
+        Regex { kind, span: Span::empty() }
     }
 }
 
@@ -88,101 +114,398 @@ impl RegexProperty {
         RegexCharSetItem::Property { negative, value: self }
     }
 
-    pub(crate) fn negative(self, negative: bool) -> Regex<'static> {
-        Regex::Property { negative, value: self }
+    pub(crate) fn negative(self, negative: bool) -> RegexKind<'static> {
+        RegexKind::Property { negative, value: self }
+    }
+}
+
+/// The state needed while generating the regex string. In readable mode, it
+/// keeps track of the current indentation. If requested, it also collects the
+/// source map. It also records which Unicode-related constructs were emitted,
+/// to determine the flags the regex needs, and the numbers of the capturing
+/// groups, which differ from pomsky's group numbers in POSIX ERE.
+pub(crate) struct CodegenState {
+    pub(crate) flavor: RegexFlavor,
+    style: OutputStyle,
+    indent: usize,
+    /// The comments in the pomsky expression that haven't been emitted yet, in
+    /// readable mode
+    comments: VecDeque<(Span, String)>,
+    source_map: Option<Vec<SourceMapEntry>>,
+    pub(crate) unicode: UnicodeUsage,
+    /// The number of capturing groups emitted so far
+    capture_count: u32,
+    /// The number of each capturing group in the regex, indexed by pomsky's
+    /// group number minus 1
+    group_numbers: Vec<u32>,
+    /// Whether the regex currently being emitted is in a lookbehind
+    pub(crate) in_lookbehind: bool,
+    error: Option<CompileError>,
+}
+
+/// Unicode-related constructs that were emitted. Depending on the flavor, they
+/// require a flag (e.g. `u` in JavaScript) to be valid or to match correctly.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct UnicodeUsage {
+    /// `\p{...}` or `\P{...}`
+    pub(crate) properties: bool,
+    /// Code points escaped with braces, e.g. `\u{1F600}` or `\x{1F600}`
+    pub(crate) braced_escapes: bool,
+    /// `\w`, `\d`, `\s`, `\b`, or their negations
+    pub(crate) shorthands: bool,
+    /// Non-ASCII characters or the dot
+    pub(crate) code_points: bool,
+}
+
+impl CodegenState {
+    pub(crate) fn new(flavor: RegexFlavor, style: OutputStyle) -> Self {
+        CodegenState {
+            flavor,
+            style,
+            indent: 0,
+            comments: VecDeque::new(),
+            source_map: None,
+            unicode: UnicodeUsage::default(),
+            capture_count: 0,
+            group_numbers: vec![],
+            in_lookbehind: false,
+            error: None,
+        }
+    }
+
+    pub(crate) fn with_source_map(self) -> Self {
+        CodegenState { source_map: Some(vec![]), ..self }
+    }
+
+    /// Collects the comments in the pomsky expression, so they can be emitted
+    /// in readable mode
+    pub(crate) fn with_comments(self, input: &str) -> Self {
+        let comments = pomsky_syntax::comments(input)
+            .into_iter()
+            .map(|span| (span, input[span.range_unchecked()].to_string()))
+            .collect();
+        CodegenState { comments, ..self }
+    }
+
+    pub(crate) fn take_source_map(&mut self) -> Option<Vec<SourceMapEntry>> {
+        self.source_map.take()
+    }
+
+    /// Adds an entry to the source map, if it is created.
+    pub(crate) fn add_source_map_entry(&mut self, output: Range<usize>, span: Span) {
+        if let Some(source_map) = &mut self.source_map {
+            if !span.is_empty() && !output.is_empty() {
+                source_map.push(SourceMapEntry { output, span });
+            }
+        }
+    }
+
+    /// Records a capturing group, which is about to be emitted.
+    pub(crate) fn add_capturing_group(&mut self) {
+        self.capture_count += 1;
+        self.group_numbers.push(self.capture_count);
+    }
+
+    /// Returns the opening parenthesis of a non-capturing group. In POSIX
+    /// ERE, which has no non-capturing groups, a capturing group is used.
+    pub(crate) fn non_capturing_group(&mut self) -> &'static str {
+        match self.flavor {
+            RegexFlavor::PosixExtended => {
+                self.capture_count += 1;
+                "("
+            }
+            RegexFlavor::Vim => "%(",
+            RegexFlavor::Emacs => "\\(?:",
+            _ => "(?:",
+        }
+    }
+
+    /// Returns the opening parenthesis of a capturing group without a name.
+    pub(crate) fn capturing_group(&self) -> &'static str {
+        if self.flavor == RegexFlavor::Emacs {
+            "\\("
+        } else {
+            "("
+        }
+    }
+
+    /// Returns the closing parenthesis of a group.
+    pub(crate) fn close_group(&self) -> &'static str {
+        if self.flavor == RegexFlavor::Emacs {
+            "\\)"
+        } else {
+            ")"
+        }
+    }
+
+    /// Records a capturing group that doesn't exist in the pomsky expression,
+    /// and returns its number.
+    pub(crate) fn add_hidden_group(&mut self) -> u32 {
+        self.capture_count += 1;
+        self.capture_count
+    }
+
+    /// Returns the number of a capturing group in the regex, given its number
+    /// in the pomsky expression. The group must have been emitted already,
+    /// unless no group was added that doesn't exist in pomsky (in POSIX ERE or
+    /// when emulating atomic groups).
+    pub(crate) fn group_number(&self, pomsky_number: u32) -> u32 {
+        self.group_numbers.get(pomsky_number as usize - 1).copied().unwrap_or(pomsky_number)
+    }
+
+    /// Records an error that can only be detected during codegen. Only the
+    /// first error is kept.
+    pub(crate) fn set_error(&mut self, error: CompileError) {
+        self.error.get_or_insert(error);
+    }
+
+    pub(crate) fn take_error(&mut self) -> Option<CompileError> {
+        self.error.take()
+    }
+
+    pub(crate) fn is_readable(&self) -> bool {
+        self.style == OutputStyle::Readable
+    }
+
+    pub(crate) fn increase_indentation(&mut self, n: usize) {
+        self.indent += n;
+    }
+
+    pub(crate) fn decrease_indentation(&mut self, n: usize) {
+        self.indent = self.indent.saturating_sub(n);
+    }
+
+    /// Starts a new line with the current indentation.
+    pub(crate) fn newline(&self, buf: &mut String) {
+        use std::fmt::Write;
+
+        write!(buf, "\n{:1$}", "", self.indent).unwrap();
+    }
+
+    /// Writes the comments that appear before `span` in the pomsky expression,
+    /// each on its own line, and starts a new line afterwards. Returns `true`
+    /// if any comment was written. Nothing is written if the span is empty.
+    pub(crate) fn comments_before(&mut self, buf: &mut String, span: Span) -> bool {
+        match span.range() {
+            Some(range) if self.write_comments(buf, range.start) => {
+                self.newline(buf);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Writes the comments that haven't been written yet, e.g. the comments at
+    /// the end of the pomsky expression.
+    pub(crate) fn remaining_comments(&mut self, buf: &mut String) {
+        self.write_comments(buf, usize::MAX);
+    }
+
+    fn write_comments(&mut self, buf: &mut String, end: usize) -> bool {
+        let mut written = false;
+        while let Some((comment, _)) = self.comments.front() {
+            if comment.range_unchecked().start >= end {
+                break;
+            }
+            let (comment, text) = self.comments.pop_front().unwrap();
+            if !CodegenState::is_line_start(buf) {
+                self.newline(buf);
+            }
+            let start = buf.len();
+            buf.push_str(&text);
+            self.add_source_map_entry(start..buf.len(), comment);
+            written = true;
+        }
+        written
+    }
+
+    /// Returns `true` if nothing except indentation was written in the current
+    /// line.
+    pub(crate) fn is_line_start(buf: &str) -> bool {
+        let line = buf.rsplit('\n').next().unwrap_or_default();
+        line.bytes().all(|b| b == b' ')
+    }
+
+    /// Writes `open`, then the content, then `close`. In readable mode, the
+    /// content is placed on its own lines and indented, unless it is empty.
+    pub(crate) fn block(
+        &mut self,
+        buf: &mut String,
+        open: &str,
+        close: &str,
+        content: impl FnOnce(&mut String, &mut Self),
+    ) {
+        buf.push_str(open);
+        if self.is_readable() {
+            let len_before = buf.len();
+            self.increase_indentation(2);
+            self.newline(buf);
+            let content_start = buf.len();
+            content(buf, self);
+            self.decrease_indentation(2);
+
+            if buf.len() == content_start {
+                buf.truncate(len_before);
+            } else {
+                self.newline(buf);
+            }
+        } else {
+            content(buf, self);
+        }
+        buf.push_str(close);
     }
 }
 
 impl<'i> Regex<'i> {
-    pub(crate) fn codegen(&self, buf: &mut String, flavor: RegexFlavor) {
-        match self {
-            Regex::Literal(l) => {
+    pub(crate) fn codegen(&self, buf: &mut String, state: &mut CodegenState) {
+        state.comments_before(buf, self.span);
+        let start = buf.len();
+        self.codegen_kind(buf, state);
+        state.add_source_map_entry(start..buf.len(), self.span);
+    }
+
+    fn codegen_kind(&self, buf: &mut String, state: &mut CodegenState) {
+        match &self.kind {
+            RegexKind::Literal(l) => {
                 for c in l.chars() {
-                    literal::codegen_char_esc(c, buf, flavor);
+                    literal::codegen_char_esc(c, buf, state);
+                }
+            }
+            RegexKind::Unescaped(u) => {
+                if state.is_readable() {
+                    // whitespace in raw regexes might be significant
+                    buf.push_str("(?-x:");
+                    buf.push_str(u);
+                    buf.push(')');
+                } else {
+                    buf.push_str(u);
                 }
             }
-            Regex::Unescaped(u) => {
-                buf.push_str(u);
+            RegexKind::Char(c) => {
+                literal::codegen_char_esc(*c, buf, state);
             }
-            &Regex::Char(c) => {
-                literal::codegen_char_esc(c, buf, flavor);
+            RegexKind::CharSet(c) => c.codegen(buf, state),
+            RegexKind::Shorthand(s) => s.codegen(buf, state),
+            RegexKind::Property { value, negative } => value.codegen(buf, *negative, state),
+            RegexKind::Grapheme => {
+                state.unicode.code_points = true;
+                buf.push_str("\\X");
             }
-            Regex::CharSet(c) => c.codegen(buf, flavor),
-            Regex::Shorthand(s) => s.codegen(buf),
-            Regex::Property { value, negative } => value.codegen(buf, *negative, flavor),
-            Regex::Grapheme => buf.push_str("\\X"),
-            Regex::Dot => buf.push('.'),
-            Regex::Group(g) => g.codegen(buf, flavor),
-            Regex::Alternation(a) => a.codegen(buf, flavor),
-            Regex::Repetition(r) => r.codegen(buf, flavor),
-            Regex::Boundary(b) => boundary_kind_codegen(*b, buf),
-            Regex::Lookaround(l) => l.codegen(buf, flavor),
-            Regex::Reference(r) => r.codegen(buf, flavor),
+            RegexKind::Dot => {
+                state.unicode.code_points = true;
+                buf.push('.');
+            }
+            RegexKind::Group(g) => g.codegen(buf, self.span, state),
+            RegexKind::Alternation(a) => a.codegen(buf, state),
+            RegexKind::Repetition(r) => r.codegen(buf, state),
+            RegexKind::Boundary(b) => boundary_kind_codegen(*b, buf, state),
+            RegexKind::Lookaround(l) => l.codegen(buf, state),
+            RegexKind::Reference(r) => r.codegen(buf, self.span, state),
+        }
+    }
+
+    /// Returns `true` if this spans multiple lines in readable mode.
+    pub(crate) fn is_block(&self) -> bool {
+        match &self.kind {
+            RegexKind::Alternation(_) | RegexKind::Lookaround(_) => true,
+            RegexKind::Group(g) => {
+                g.kind != RegexGroupKind::Normal || g.parts.iter().any(Regex::is_block)
+            }
+            RegexKind::Repetition(r) => r.content.is_block(),
+            _ => false,
         }
     }
 
     pub(crate) fn needs_parens_in_sequence(&self) -> bool {
-        match self {
-            Regex::Alternation(_) => true,
-            Regex::Literal(_)
-            | Regex::Unescaped(_)
-            | Regex::Char(_)
-            | Regex::Group(_)
-            | Regex::CharSet(_)
-            | Regex::Grapheme
-            | Regex::Repetition(_)
-            | Regex::Boundary(_)
-            | Regex::Lookaround(_)
-            | Regex::Reference(_)
-            | Regex::Shorthand(_)
-            | Regex::Property { .. }
-            | Regex::Dot => false,
+        match &self.kind {
+            RegexKind::Alternation(_) => true,
+            RegexKind::Literal(_)
+            | RegexKind::Unescaped(_)
+            | RegexKind::Char(_)
+            | RegexKind::Group(_)
+            | RegexKind::CharSet(_)
+            | RegexKind::Grapheme
+            | RegexKind::Repetition(_)
+            | RegexKind::Boundary(_)
+            | RegexKind::Lookaround(_)
+            | RegexKind::Reference(_)
+            | RegexKind::Shorthand(_)
+            | RegexKind::Property { .. }
+            | RegexKind::Dot => false,
         }
     }
 
     pub(crate) fn needs_parens_before_repetition(&self, flavor: RegexFlavor) -> bool {
-        match self {
-            Regex::Literal(l) => literal::needs_parens_before_repetition(l.borrow()),
-            Regex::Group(g) => g.needs_parens_before_repetition(flavor),
-            Regex::Repetition(_)
-            | Regex::Alternation(_)
-            | Regex::Boundary(_)
-            | Regex::Unescaped(_) => true,
-            Regex::Lookaround(_) => matches!(flavor, RegexFlavor::JavaScript),
-            Regex::CharSet(_)
-            | Regex::Char(_)
-            | Regex::Grapheme
-            | Regex::Reference(_)
-            | Regex::Shorthand(_)
-            | Regex::Property { .. }
-            | Regex::Dot => false,
+        match &self.kind {
+            RegexKind::Literal(l) => literal::needs_parens_before_repetition(l.borrow()),
+            RegexKind::Group(g) => g.needs_parens_before_repetition(flavor),
+            RegexKind::Repetition(_)
+            | RegexKind::Alternation(_)
+            | RegexKind::Boundary(_)
+            | RegexKind::Unescaped(_) => true,
+            // in Vim, the lookaround is already a quantifier, which can't be repeated
+            RegexKind::Lookaround(_) => {
+                matches!(flavor, RegexFlavor::JavaScript | RegexFlavor::Vim)
+            }
+            RegexKind::CharSet(_)
+            | RegexKind::Char(_)
+            | RegexKind::Grapheme
+            | RegexKind::Reference(_)
+            | RegexKind::Shorthand(_)
+            | RegexKind::Property { .. }
+            | RegexKind::Dot => false,
         }
     }
 
     pub(crate) fn result_is_empty(&self) -> bool {
-        match self {
-            Regex::Literal(l) => l.is_empty(),
-            Regex::Group(g) => g.parts.iter().all(Regex::result_is_empty),
-            Regex::Unescaped(r) => r.is_empty(),
+        match &self.kind {
+            RegexKind::Literal(l) => l.is_empty(),
+            RegexKind::Group(g) => g.parts.iter().all(Regex::result_is_empty),
+            RegexKind::Unescaped(r) => r.is_empty(),
             _ => false,
         }
     }
 
+    /// Returns `false` if this can match an arbitrarily long string, e.g.
+    /// because it contains a repetition without an upper bound.
+    pub(crate) fn has_bounded_length(&self) -> bool {
+        match &self.kind {
+            RegexKind::Repetition(r) => {
+                r.kind.upper_bound.is_some() && r.content.has_bounded_length()
+            }
+            RegexKind::Group(g) => g.parts.iter().all(Regex::has_bounded_length),
+            RegexKind::Alternation(a) => a.parts.iter().all(Regex::has_bounded_length),
+            RegexKind::Grapheme | RegexKind::Reference(_) => false,
+            RegexKind::Literal(_)
+            | RegexKind::Unescaped(_)
+            | RegexKind::Char(_)
+            | RegexKind::CharSet(_)
+            | RegexKind::Shorthand(_)
+            | RegexKind::Property { .. }
+            | RegexKind::Dot
+            | RegexKind::Boundary(_)
+            | RegexKind::Lookaround(_) => true,
+        }
+    }
+
     pub(crate) fn is_assertion(&self) -> bool {
-        match self {
-            Regex::Lookaround(_) | Regex::Boundary(_) => true,
-            Regex::Group(g) if matches!(g.kind, RegexGroupKind::Normal) => {
+        match &self.kind {
+            RegexKind::Lookaround(_) | RegexKind::Boundary(_) => true,
+            RegexKind::Group(g) if matches!(g.kind, RegexGroupKind::Normal) => {
                 let mut iter = g.parts.iter().filter(|part| !part.result_is_empty());
                 iter.next().map_or(false, Regex::is_assertion) && iter.next().is_none()
             }
-            Regex::Alternation(g) => g.parts.iter().any(Regex::is_assertion),
+            RegexKind::Alternation(g) => g.parts.iter().any(Regex::is_assertion),
             _ => false,
         }
     }
 }
 
 impl RegexShorthand {
-    pub(crate) fn codegen(self, buf: &mut String) {
+    pub(crate) fn codegen(self, buf: &mut String, state: &mut CodegenState) {
+        if !matches!(self, RegexShorthand::VertSpace | RegexShorthand::HorizSpace) {
+            state.unicode.shorthands = true;
+        }
         match self {
             RegexShorthand::Word => buf.push_str("\\w"),
             RegexShorthand::Digit => buf.push_str("\\d"),
@@ -197,7 +520,9 @@ impl RegexShorthand {
 }
 
 impl RegexProperty {
-    pub(crate) fn codegen(self, buf: &mut String, negative: bool, flavor: RegexFlavor) {
+    pub(crate) fn codegen(self, buf: &mut String, negative: bool, state: &mut CodegenState) {
+        let flavor = state.flavor;
+        state.unicode.properties = true;
         if negative {
             buf.push_str("\\P{");
         } else {
@@ -206,12 +531,18 @@ impl RegexProperty {
         match self {
             RegexProperty::Category(c) => buf.push_str(c.as_str()),
             RegexProperty::Script(s) => {
-                if flavor == RegexFlavor::JavaScript {
-                    buf.push_str("sc=");
+                match flavor {
+                    RegexFlavor::JavaScript => buf.push_str("sc="),
+                    RegexFlavor::Icu | RegexFlavor::PythonRegex => buf.push_str("Script="),
+                    _ => {}
                 }
                 buf.push_str(s.as_str());
             }
             RegexProperty::Block(b) => match flavor {
+                RegexFlavor::Icu | RegexFlavor::PythonRegex => {
+                    buf.push_str("Block=");
+                    buf.push_str(&b.as_str().replace('-', "_"));
+                }
                 RegexFlavor::DotNet => {
                     buf.push_str("Is");
                     buf.push_str(&b.as_str().replace('_', ""));
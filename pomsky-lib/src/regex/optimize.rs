@@ -4,7 +4,7 @@ use pomsky_syntax::exprs::RepetitionKind;
 
 use crate::exprs::group::RegexGroupKind;
 
-use super::Regex;
+use super::{Regex, RegexKind};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Count {
@@ -27,8 +27,8 @@ impl Add for Count {
 
 impl<'i> Regex<'i> {
     pub(crate) fn optimize(&mut self) -> Count {
-        match self {
-            Regex::Literal(l) => {
+        match &mut self.kind {
+            RegexKind::Literal(l) => {
                 let l: &str = &*l;
                 if l.is_empty() {
                     // indicates that the parent should remove it
@@ -39,7 +39,7 @@ impl<'i> Regex<'i> {
                     Count::Many
                 }
             }
-            Regex::Group(g) => {
+            RegexKind::Group(g) => {
                 let mut count = Count::Zero;
                 g.parts.retain_mut(|part| {
                     let add = part.optimize();
@@ -49,10 +49,11 @@ impl<'i> Regex<'i> {
 
                 if g.parts.len() == 1
                     && g.kind == RegexGroupKind::Normal
-                    && !matches!(&g.parts[0], Regex::Unescaped(_))
+                    && !matches!(&g.parts[0].kind, RegexKind::Unescaped(_))
                 {
                     // don't remove group if it is wrapping raw regex
-                    *self = g.parts.pop().unwrap();
+                    let part = g.parts.pop().unwrap();
+                    self.replace_with(part);
                     return count;
                 }
 
@@ -65,9 +66,10 @@ impl<'i> Regex<'i> {
                     count
                 }
             }
-            Regex::Alternation(a) => {
+            RegexKind::Alternation(a) => {
                 if a.parts.len() == 1 {
-                    *self = a.parts.pop().unwrap();
+                    let part = a.parts.pop().unwrap();
+                    self.replace_with(part);
                     return self.optimize();
                 }
 
@@ -77,9 +79,10 @@ impl<'i> Regex<'i> {
 
                 Count::One
             }
-            Regex::Repetition(r) => {
+            RegexKind::Repetition(r) => {
                 if r.kind.lower_bound == 1 && r.kind.upper_bound == Some(1) {
-                    *self = mem::take(&mut r.content);
+                    let content = mem::take(&mut r.content);
+                    self.replace_with(content);
                     return self.optimize();
                 }
 
@@ -88,11 +91,13 @@ impl<'i> Regex<'i> {
                         // indicates that the parent should remove it
                         return Count::Zero;
                     }
-                    Count::One => match &mut r.content {
-                        Regex::Repetition(inner) if inner.quantifier == r.quantifier => {
+                    Count::One => match &mut r.content.kind {
+                        RegexKind::Repetition(inner) if inner.quantifier == r.quantifier => {
                             if let Some(kind) = reduce_repetitions(r.kind, inner.kind) {
                                 inner.kind = kind;
-                                *self = mem::take(&mut r.content);
+                                // the merged repetition corresponds to the outer one
+                                let span = self.span;
+                                *self = Regex { span, ..mem::take(&mut r.content) };
                             }
                         }
                         _ => {}
@@ -102,21 +107,30 @@ impl<'i> Regex<'i> {
 
                 Count::One
             }
-            Regex::Lookaround(l) => {
+            RegexKind::Lookaround(l) => {
                 l.content.optimize();
                 Count::One
             }
-            Regex::Unescaped(_) => Count::Many,
-            Regex::Char(_)
-            | Regex::CharSet(_)
-            | Regex::Shorthand(_)
-            | Regex::Property { .. }
-            | Regex::Grapheme
-            | Regex::Dot
-            | Regex::Boundary(_)
-            | Regex::Reference(_) => Count::One,
+            RegexKind::Unescaped(_) => Count::Many,
+            RegexKind::Char(_)
+            | RegexKind::CharSet(_)
+            | RegexKind::Shorthand(_)
+            | RegexKind::Property { .. }
+            | RegexKind::Grapheme
+            | RegexKind::Dot
+            | RegexKind::Boundary(_)
+            | RegexKind::Reference(_) => Count::One,
         }
     }
+
+    /// Replaces this node with one of its children. The child inherits this
+    /// node's span if it doesn't have one.
+    fn replace_with(&mut self, mut child: Regex<'i>) {
+        if child.span.is_empty() {
+            child.span = self.span;
+        }
+        *self = child;
+    }
 }
 
 fn reduce_repetitions(outer: RepetitionKind, inner: RepetitionKind) -> Option<RepetitionKind> {
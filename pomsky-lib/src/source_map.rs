@@ -0,0 +1,78 @@
+//! Contains the [`SourceMap`], which maps parts of a compiled regex back to the
+//! pomsky expression they were compiled from.
+
+use std::{cmp::Reverse, ops::Range};
+
+use pomsky_syntax::Span;
+
+/// A mapping from byte ranges in a compiled regex to the spans of the pomsky
+/// rules that produced them.
+///
+/// Entries can be nested, e.g. the output of a group contains the output of
+/// its parts. The entries are sorted by their start offset in the regex, and
+/// entries with the same start are sorted from the outermost to the innermost.
+///
+/// Rules without a span, such as the dot (`.`), have no entry of their own.
+/// They are covered by the entry of the surrounding rule, if it exists.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    entries: Vec<SourceMapEntry>,
+}
+
+/// An entry in a [`SourceMap`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceMapEntry {
+    /// The byte range in the compiled regex
+    pub output: Range<usize>,
+    /// The span of the pomsky rule that produced this part of the regex
+    pub span: Span,
+}
+
+impl SourceMap {
+    pub(crate) fn new(mut entries: Vec<SourceMapEntry>) -> Self {
+        entries.sort_by_key(|e| (e.output.start, Reverse(e.output.end)));
+        SourceMap { entries }
+    }
+
+    /// Returns all entries, ordered by their position in the regex.
+    pub fn entries(&self) -> &[SourceMapEntry] {
+        &self.entries
+    }
+
+    /// Returns the span of the innermost pomsky rule that produced the byte at
+    /// the given offset in the regex, or `None` if no rule with a span covers
+    /// it.
+    pub fn span_at(&self, offset: usize) -> Option<Span> {
+        self.entries.iter().rev().find(|e| e.output.contains(&offset)).map(|e| e.span)
+    }
+}
+
+#[test]
+fn test_source_map() {
+    use crate::{options::CompileOptions, Expr};
+
+    let input = "let x = 'a'; :(x | 'bc')+";
+    let (expr, _) = Expr::parse(input);
+    let (output, _) = expr.unwrap().compile_with_source_map(input, CompileOptions::default());
+    let (regex, map) = output.unwrap();
+    assert_eq!(regex, "(a|bc)+");
+
+    let spans = map
+        .entries()
+        .iter()
+        .map(|e| (&regex[e.output.clone()], &input[e.span.range_unchecked()]))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        spans,
+        [
+            ("(a|bc)+", ":(x | 'bc')+"),
+            ("(a|bc)", ":(x | 'bc')"),
+            ("a|bc", "x | 'bc'"),
+            ("a", "'a'"),
+            ("bc", "'bc'"),
+        ]
+    );
+
+    assert_eq!(map.span_at(3), Some(Span::new(19, 23)));
+    assert_eq!(map.span_at(6), Some(Span::new(13, 25)));
+}
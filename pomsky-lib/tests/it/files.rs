@@ -6,7 +6,7 @@ use std::{
 
 use pomsky::{
     diagnose::{Diagnostic, Severity},
-    options::{CompileOptions, RegexFlavor},
+    options::{CompileOptions, EcmaVersion, FlavorVersion, OutputStyle, RegexFlavor},
 };
 use regex_test::r#async::RegexTest;
 use tokio::task::spawn_blocking;
@@ -26,6 +26,14 @@ pub(crate) enum TestResult {
 struct Options {
     /// The regex flavor to compile with
     flavor: RegexFlavor,
+    /// How the regex is formatted
+    style: OutputStyle,
+    /// The version of the regex flavor
+    version: Option<FlavorVersion>,
+    /// Whether `Grapheme` is emulated
+    emulate_grapheme: bool,
+    /// Whether atomic groups are emulated
+    emulate_atomic_groups: bool,
     /// Whether this test should be ignored entirely
     ignore: bool,
     /// Whether we expect a compilation error from pomsky or not
@@ -40,6 +48,10 @@ impl Default for Options {
     fn default() -> Self {
         Self {
             flavor: RegexFlavor::Rust,
+            style: OutputStyle::Minimal,
+            version: None,
+            emulate_grapheme: false,
+            emulate_atomic_groups: false,
             ignore: false,
             expected_outcome: Outcome::Success,
             compile: true,
@@ -63,8 +75,14 @@ impl Options {
                         "java" => RegexFlavor::Java,
                         ".net" | "dotnet" => RegexFlavor::DotNet,
                         "python" => RegexFlavor::Python,
+                        "pythonregex" => RegexFlavor::PythonRegex,
                         "rust" => RegexFlavor::Rust,
                         "ruby" => RegexFlavor::Ruby,
+                        "re2" => RegexFlavor::RE2,
+                        "posixextended" | "ere" => RegexFlavor::PosixExtended,
+                        "icu" => RegexFlavor::Icu,
+                        "vim" => RegexFlavor::Vim,
+                        "emacs" => RegexFlavor::Emacs,
                         _ => {
                             eprintln!("{}: Unknown flavor {value:?}", Yellow("Warning"));
                             eprintln!("  in {path:?}");
@@ -72,6 +90,31 @@ impl Options {
                         }
                     };
                 }
+                "version" => {
+                    result.version = Some(match value.to_ascii_lowercase().as_str() {
+                        "es2015" => FlavorVersion::JavaScript(EcmaVersion::Es2015),
+                        "es2018" => FlavorVersion::JavaScript(EcmaVersion::Es2018),
+                        v => match v.split_once('.').map(|(a, b)| (a.parse(), b.parse())) {
+                            Some((Ok(major), Ok(minor))) => FlavorVersion::Python(major, minor),
+                            _ => {
+                                eprintln!("{}: Unknown version {value:?}", Yellow("Warning"));
+                                eprintln!("  in {path:?}");
+                                continue;
+                            }
+                        },
+                    });
+                }
+                "style" => {
+                    result.style = match value {
+                        "minimal" => OutputStyle::Minimal,
+                        "readable" => OutputStyle::Readable,
+                        _ => {
+                            eprintln!("{}: Unknown output style {value:?}", Yellow("Warning"));
+                            eprintln!("  in {path:?}");
+                            continue;
+                        }
+                    };
+                }
                 "expect" => {
                     result.expected_outcome = match value {
                         "success" => Outcome::Success,
@@ -83,6 +126,28 @@ impl Options {
                         }
                     }
                 }
+                "emulate_grapheme" => {
+                    result.emulate_grapheme = match value {
+                        "yes" | "true" | "" => true,
+                        "no" | "false" => false,
+                        _ => {
+                            eprintln!("{}: Unknown boolean {value:?}", Yellow("Warning"));
+                            eprintln!("  in {path:?}");
+                            continue;
+                        }
+                    }
+                }
+                "emulate_atomic_groups" => {
+                    result.emulate_atomic_groups = match value {
+                        "yes" | "true" | "" => true,
+                        "no" | "false" => false,
+                        _ => {
+                            eprintln!("{}: Unknown boolean {value:?}", Yellow("Warning"));
+                            eprintln!("  in {path:?}");
+                            continue;
+                        }
+                    }
+                }
                 "ignore" | "ignored" => {
                     result.ignore = match value {
                         "yes" | "true" | "" => true,
@@ -153,7 +218,14 @@ pub(crate) async fn test_file(
     let parsed = spawn_blocking(move || {
         pomsky::Expr::parse_and_compile(
             &input_owned,
-            CompileOptions { flavor: options.flavor, ..Default::default() },
+            CompileOptions {
+                flavor: options.flavor,
+                output_style: options.style,
+                flavor_version: options.version,
+                emulate_grapheme: options.emulate_grapheme,
+                emulate_atomic_groups: options.emulate_atomic_groups,
+                ..Default::default()
+            },
         )
     })
     .await
@@ -283,6 +355,24 @@ fn create_content(input: &str, outcome: &str, options: Options) -> String {
     if options.flavor != RegexFlavor::Rust {
         option_strings.push(format!("flavor={:?}", options.flavor));
     }
+    match options.version {
+        Some(FlavorVersion::Python(major, minor)) => {
+            option_strings.push(format!("version={major}.{minor}"));
+        }
+        Some(FlavorVersion::JavaScript(version)) => {
+            option_strings.push(format!("version={version:?}"));
+        }
+        _ => {}
+    }
+    if options.style == OutputStyle::Readable {
+        option_strings.push(String::from("style=readable"));
+    }
+    if options.emulate_grapheme {
+        option_strings.push(String::from("emulate_grapheme"));
+    }
+    if options.emulate_atomic_groups {
+        option_strings.push(String::from("emulate_atomic_groups"));
+    }
 
     let option_strings = if option_strings.is_empty() {
         "".to_string()
@@ -61,8 +61,14 @@ mod diagnostic;
 /// - **JavaScript** (ECMAScript, Dart)
 /// - **Pcre** (Crystal, Delphi, Elixir, Erlang, Hack, Julia, PHP, R, Vala, ...)
 /// - **Python** (`re` module)
+/// - **PythonRegex** (third-party `regex` module)
 /// - **Ruby**
 /// - **Rust** (`regex` crate)
+/// - **RE2** (also Go's `regexp` package)
+/// - **PosixExtended** (POSIX ERE, used by `grep -E`, `awk` and `sed -E`)
+/// - **Icu** (Swift's `NSRegularExpression`, MySQL 8, R's `stringi` package)
+/// - **Vim**
+/// - **Emacs**
 ///
 /// [pomsky]: https://pomsky-lang.org
 /// [regex]: https://docs.rs/regex
@@ -186,10 +192,17 @@ fn get_flavor(item: Option<TokenTree>) -> Result<RegexFlavor, Error> {
             "JavaScript" => RegexFlavor::JavaScript,
             "Pcre" => RegexFlavor::Pcre,
             "Python" => RegexFlavor::Python,
+            "PythonRegex" => RegexFlavor::PythonRegex,
             "Ruby" => RegexFlavor::Ruby,
             "Rust" => RegexFlavor::Rust,
+            "RE2" => RegexFlavor::RE2,
+            "PosixExtended" => RegexFlavor::PosixExtended,
+            "Icu" => RegexFlavor::Icu,
+            "Vim" => RegexFlavor::Vim,
+            "Emacs" => RegexFlavor::Emacs,
             s => bail!(
-                "Expected one of: DotNet, Java, JavaScript, Pcre, Python, Ruby, Rust\nGot: {s}",
+                "Expected one of: DotNet, Java, JavaScript, Pcre, Python, PythonRegex, Ruby, Rust, RE2, \
+                PosixExtended, Icu, Vim, Emacs\nGot: {s}",
                 id.span()
             ),
         },
@@ -11,57 +11,126 @@ pub(super) fn parse_ascii_group(
     }
 
     Ok(match name {
-        "ascii_alpha" => {
-            vec![GroupItem::range_unchecked('a', 'z'), GroupItem::range_unchecked('A', 'Z')]
-        }
-        "ascii_alnum" => vec![
-            GroupItem::range_unchecked('0', '9'),
-            GroupItem::range_unchecked('a', 'z'),
-            GroupItem::range_unchecked('A', 'Z'),
-        ],
         "ascii" => vec![GroupItem::range_unchecked('\0', '\x7F')],
-        "ascii_blank" => vec![GroupItem::Char(' '), GroupItem::Char('\t')],
-        "ascii_cntrl" => vec![GroupItem::range_unchecked('\0', '\x1F'), GroupItem::Char('\x7F')],
-        "ascii_digit" => vec![GroupItem::range_unchecked('0', '9')],
-        "ascii_graph" => vec![GroupItem::range_unchecked('!', '~')],
-        "ascii_lower" => vec![GroupItem::range_unchecked('a', 'z')],
-        "ascii_print" => vec![GroupItem::range_unchecked(' ', '~')],
-        "ascii_punct" => vec![
-            GroupItem::range_unchecked('!', '/'),
-            GroupItem::range_unchecked(':', '@'),
-            GroupItem::range_unchecked('[', '`'),
-            GroupItem::range_unchecked('{', '~'),
-        ],
-        "ascii_space" => vec![
-            GroupItem::Char(' '),
-            GroupItem::Char('\t'),
-            GroupItem::Char('\n'),
-            GroupItem::Char('\r'),
-            GroupItem::Char('\x0B'),
-            GroupItem::Char('\x0C'),
-        ],
-        "ascii_upper" => vec![GroupItem::range_unchecked('A', 'Z')],
         "ascii_word" => vec![
             GroupItem::range_unchecked('0', '9'),
             GroupItem::range_unchecked('a', 'z'),
             GroupItem::range_unchecked('A', 'Z'),
             GroupItem::Char('_'),
         ],
-        "ascii_xdigit" => vec![
-            GroupItem::range_unchecked('0', '9'),
-            GroupItem::range_unchecked('a', 'f'),
-            GroupItem::range_unchecked('A', 'F'),
-        ],
-        _ => {
-            return Err(CharClassError::UnknownNamedClass {
-                found: name.into(),
-                #[cfg(feature = "suggestions")]
-                similar: crate::util::find_suggestion(name, OPTION_LIST.iter().copied()),
-            })
-        }
+        _ => match name.strip_prefix("ascii_").and_then(PosixClass::from_name) {
+            Some(class) => class.ascii_items(),
+            None => {
+                return Err(CharClassError::UnknownNamedClass {
+                    found: name.into(),
+                    #[cfg(feature = "suggestions")]
+                    similar: crate::util::find_suggestion(name, OPTION_LIST.iter().copied()),
+                })
+            }
+        },
     })
 }
 
+/// A character class from POSIX, such as `[:alpha:]`. The `ascii_*` classes
+/// in pomsky (except `ascii` and `ascii_word`) correspond to them, but they
+/// only match ASCII characters, whereas POSIX classes depend on the locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PosixClass {
+    Alnum,
+    Alpha,
+    Blank,
+    Cntrl,
+    Digit,
+    Graph,
+    Lower,
+    Print,
+    Punct,
+    Space,
+    Upper,
+    XDigit,
+}
+
+impl PosixClass {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "alnum" => PosixClass::Alnum,
+            "alpha" => PosixClass::Alpha,
+            "blank" => PosixClass::Blank,
+            "cntrl" => PosixClass::Cntrl,
+            "digit" => PosixClass::Digit,
+            "graph" => PosixClass::Graph,
+            "lower" => PosixClass::Lower,
+            "print" => PosixClass::Print,
+            "punct" => PosixClass::Punct,
+            "space" => PosixClass::Space,
+            "upper" => PosixClass::Upper,
+            "xdigit" => PosixClass::XDigit,
+            _ => return None,
+        })
+    }
+
+    /// Returns the name of the class as used in POSIX bracket expressions,
+    /// e.g. `alpha` for `[:alpha:]`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PosixClass::Alnum => "alnum",
+            PosixClass::Alpha => "alpha",
+            PosixClass::Blank => "blank",
+            PosixClass::Cntrl => "cntrl",
+            PosixClass::Digit => "digit",
+            PosixClass::Graph => "graph",
+            PosixClass::Lower => "lower",
+            PosixClass::Print => "print",
+            PosixClass::Punct => "punct",
+            PosixClass::Space => "space",
+            PosixClass::Upper => "upper",
+            PosixClass::XDigit => "xdigit",
+        }
+    }
+
+    /// Returns the characters matched by this class in the POSIX locale
+    pub fn ascii_items(self) -> Vec<GroupItem> {
+        match self {
+            PosixClass::Alnum => vec![
+                GroupItem::range_unchecked('0', '9'),
+                GroupItem::range_unchecked('a', 'z'),
+                GroupItem::range_unchecked('A', 'Z'),
+            ],
+            PosixClass::Alpha => {
+                vec![GroupItem::range_unchecked('a', 'z'), GroupItem::range_unchecked('A', 'Z')]
+            }
+            PosixClass::Blank => vec![GroupItem::Char(' '), GroupItem::Char('\t')],
+            PosixClass::Cntrl => {
+                vec![GroupItem::range_unchecked('\0', '\x1F'), GroupItem::Char('\x7F')]
+            }
+            PosixClass::Digit => vec![GroupItem::range_unchecked('0', '9')],
+            PosixClass::Graph => vec![GroupItem::range_unchecked('!', '~')],
+            PosixClass::Lower => vec![GroupItem::range_unchecked('a', 'z')],
+            PosixClass::Print => vec![GroupItem::range_unchecked(' ', '~')],
+            PosixClass::Punct => vec![
+                GroupItem::range_unchecked('!', '/'),
+                GroupItem::range_unchecked(':', '@'),
+                GroupItem::range_unchecked('[', '`'),
+                GroupItem::range_unchecked('{', '~'),
+            ],
+            PosixClass::Space => vec![
+                GroupItem::Char(' '),
+                GroupItem::Char('\t'),
+                GroupItem::Char('\n'),
+                GroupItem::Char('\r'),
+                GroupItem::Char('\x0B'),
+                GroupItem::Char('\x0C'),
+            ],
+            PosixClass::Upper => vec![GroupItem::range_unchecked('A', 'Z')],
+            PosixClass::XDigit => vec![
+                GroupItem::range_unchecked('0', '9'),
+                GroupItem::range_unchecked('a', 'f'),
+                GroupItem::range_unchecked('A', 'F'),
+            ],
+        }
+    }
+}
+
 #[cfg(feature = "suggestions")]
 const OPTION_LIST: &[&str] = &[
     "ascii_alpha",
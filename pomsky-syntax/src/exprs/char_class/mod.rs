@@ -78,6 +78,7 @@
 
 use crate::{error::ParseErrorKind, Span};
 
+pub use ascii::PosixClass;
 pub use char_group::{CharGroup, GroupItem, GroupName};
 pub use unicode::{Category, CodeBlock, OtherProperties, Script};
 
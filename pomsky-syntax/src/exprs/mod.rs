@@ -18,7 +18,8 @@ pub use self::{
     alternation::Alternation,
     boundary::{Boundary, BoundaryKind},
     char_class::{
-        Category, CharClass, CharGroup, CodeBlock, GroupItem, GroupName, OtherProperties, Script,
+        Category, CharClass, CharGroup, CodeBlock, GroupItem, GroupName, OtherProperties,
+        PosixClass, Script,
     },
     group::{Capture, Group, GroupKind},
     literal::Literal,
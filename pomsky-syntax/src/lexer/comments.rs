@@ -0,0 +1,34 @@
+use crate::Span;
+
+use super::tokenize;
+
+/// Returns the spans of all line comments in a pomsky expression, in source
+/// order. Each span starts at the `#` and ends before the line break.
+///
+/// Comments can only appear between tokens, so the gaps between the tokens
+/// are searched for them. This means that a `#` within a string is never
+/// mistaken for a comment.
+pub fn comments(source: &str) -> Vec<Span> {
+    let mut result = vec![];
+    let mut offset = 0;
+
+    for (_, span) in tokenize(source) {
+        let range = span.range_unchecked();
+        find_comments(&source[offset..range.start], offset, &mut result);
+        offset = range.end;
+    }
+    find_comments(&source[offset..], offset, &mut result);
+
+    result
+}
+
+fn find_comments(gap: &str, offset: usize, buf: &mut Vec<Span>) {
+    let mut rest = gap;
+    while let Some(start) = rest.find('#') {
+        let len = rest[start..].find('\n').unwrap_or(rest.len() - start);
+        let comment_offset = offset + (gap.len() - rest.len()) + start;
+        let comment = rest[start..start + len].trim_end();
+        buf.push(Span::new(comment_offset, comment_offset + comment.len()));
+        rest = &rest[start + len..];
+    }
+}
@@ -8,6 +8,8 @@
 use super::LexErrorMsg;
 
 pub(super) fn get_parse_error_msg_help(msg: LexErrorMsg, slice: &str) -> Option<String> {
+    let replacement = get_parse_error_msg_replacement(msg, slice);
+
     Some(match msg {
         LexErrorMsg::GroupNonCapturing => "Non-capturing groups are just parentheses: `(...)`. \
             Capturing groups use the `:(...)` syntax."
@@ -29,15 +31,14 @@ pub(super) fn get_parse_error_msg_help(msg: LexErrorMsg, slice: &str) -> Option<
             .into(),
         LexErrorMsg::GroupNamedCapture => return Some(get_named_capture_help(slice)),
         LexErrorMsg::GroupPcreBackreference => return Some(get_pcre_backreference_help(slice)),
-        LexErrorMsg::Backslash => return get_backslash_help(slice),
-        LexErrorMsg::BackslashU4 => return Some(get_backslash_help_u4(slice)),
-        LexErrorMsg::BackslashX2 => return Some(get_backslash_help_x2(slice)),
-        LexErrorMsg::BackslashUnicode => return Some(get_backslash_help_unicode(slice)),
-        LexErrorMsg::BackslashGK => return Some(get_backslash_gk_help(slice)),
-        LexErrorMsg::BackslashProperty => return Some(get_backslash_property_help(slice)),
+        LexErrorMsg::Backslash => return get_backslash_help(slice, replacement),
+        LexErrorMsg::BackslashU4 | LexErrorMsg::BackslashX2 | LexErrorMsg::BackslashUnicode => {
+            format!("Try `{}` instead", replacement?)
+        }
+        LexErrorMsg::BackslashGK => return get_backslash_gk_help(slice, replacement),
+        LexErrorMsg::BackslashProperty => format!("Replace `{slice}` with `{}`", replacement?),
 
-        LexErrorMsg::DeprStart => return Some("Use `^` instead".into()),
-        LexErrorMsg::DeprEnd => return Some("Use `$` instead".into()),
+        LexErrorMsg::DeprStart | LexErrorMsg::DeprEnd => format!("Use `{}` instead", replacement?),
 
         LexErrorMsg::GroupAtomic
         | LexErrorMsg::GroupConditional
@@ -48,6 +49,104 @@ pub(super) fn get_parse_error_msg_help(msg: LexErrorMsg, slice: &str) -> Option<
     })
 }
 
+pub(super) fn get_parse_error_msg_replacement(msg: LexErrorMsg, slice: &str) -> Option<String> {
+    Some(match msg {
+        LexErrorMsg::GroupNonCapturing => "(".into(),
+        LexErrorMsg::GroupLookahead => "(>>".into(),
+        LexErrorMsg::GroupLookaheadNeg => "(!>>".into(),
+        LexErrorMsg::GroupLookbehind => "(<<".into(),
+        LexErrorMsg::GroupLookbehindNeg => "(!<<".into(),
+        LexErrorMsg::GroupNamedCapture => {
+            // (?<name>, (?P<name>, (?'name'
+            let name = slice
+                .trim_start_matches("(?")
+                .trim_start_matches('P')
+                .trim_matches(&['<', '>', '\''][..]);
+            if !is_name(name) {
+                return None;
+            }
+            format!(":{name}(")
+        }
+        LexErrorMsg::GroupPcreBackreference => {
+            // (?P=name)
+            let name = slice.trim_start_matches("(?P=").trim_end_matches(')');
+            if !is_name(name) {
+                return None;
+            }
+            format!("::{name}")
+        }
+        LexErrorMsg::Backslash => return get_backslash_replacement(slice),
+        LexErrorMsg::BackslashU4 | LexErrorMsg::BackslashX2 => format!("U+{}", &slice[2..]),
+        LexErrorMsg::BackslashUnicode => {
+            format!("U+{}", slice[2..].trim_matches(&['{', '}'][..]))
+        }
+        LexErrorMsg::BackslashGK => {
+            // \k<name>, \g{name}, \g-1, ...
+            let name = slice[2..].trim_matches(&['{', '}', '<', '>', '\''][..]);
+            let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+            let is_valid = match name.strip_prefix(['-', '+']) {
+                Some(number) => is_number(number),
+                None => name != "0" && (is_number(name) || is_name(name)),
+            };
+            if !is_valid {
+                return None;
+            }
+            format!("::{name}")
+        }
+        LexErrorMsg::BackslashProperty => {
+            let is_negative = (slice.starts_with("\\P") && !slice.starts_with("\\P{^"))
+                || slice.starts_with("\\p{^");
+            let name = slice[2..].trim_matches(&['{', '}', '^'][..]).replace(&['+', '-'][..], "_");
+            if is_negative {
+                format!("[!{name}]")
+            } else {
+                format!("[{name}]")
+            }
+        }
+
+        LexErrorMsg::DeprStart => "^".into(),
+        LexErrorMsg::DeprEnd => "$".into(),
+
+        LexErrorMsg::GroupComment
+        | LexErrorMsg::GroupAtomic
+        | LexErrorMsg::GroupConditional
+        | LexErrorMsg::GroupBranchReset
+        | LexErrorMsg::GroupSubroutineCall
+        | LexErrorMsg::GroupOther
+        | LexErrorMsg::UnclosedString => return None,
+    })
+}
+
+/// Whether the name of a regex group can be used as the name of a pomsky group
+fn is_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Returns the replacement of a backslash escape if it is unambiguous, e.g.
+/// not for `\1`, which could be a backreference or an octal escape
+fn get_backslash_replacement(str: &str) -> Option<String> {
+    let mut iter = str.strip_prefix('\\')?.chars();
+
+    Some(match iter.next() {
+        Some('b') => "%".into(),
+        Some('B') => "!%".into(),
+        Some('A') => "Start".into(),
+        Some('z') => "End".into(),
+        Some('N') => "![n]".into(),
+        Some('X') => "Grapheme".into(),
+        Some('R') => "([r] [n] | [v])".into(),
+        Some(c @ ('D' | 'W' | 'S')) => format!("[!{}]", c.to_ascii_lowercase()),
+        Some(c @ ('V' | 'H')) => format!("![{}]", c.to_ascii_lowercase()),
+        Some(c @ ('a' | 'e' | 'f' | 'n' | 'r' | 't' | 'h' | 'v' | 'd' | 'w' | 's')) => {
+            format!("[{c}]")
+        }
+        Some('0') => "U+00".into(),
+        Some(c @ ('8' | '9')) => format!("::{c}"),
+        _ => return None,
+    })
+}
+
 fn get_named_capture_help(str: &str) -> String {
     // (?<name>), (?P<name>)
     let name =
@@ -66,80 +165,43 @@ fn get_pcre_backreference_help(str: &str) -> String {
     format!("Backreferences use the `::name` syntax. Try `::{name}` instead")
 }
 
-fn get_backslash_help(str: &str) -> Option<String> {
-    assert!(str.starts_with('\\'));
-    let str = &str[1..];
-    let mut iter = str.chars();
+/// Returns the help for a backslash escape. When there's an unambiguous
+/// replacement, the help suggests it.
+fn get_backslash_help(str: &str, replacement: Option<String>) -> Option<String> {
+    let c = str.strip_prefix('\\')?.chars().next()?;
+
+    if let Some(replacement) = replacement {
+        let purpose = match c {
+            'b' => " to match a word boundary",
+            'B' => " to match a place without a word boundary",
+            'A' => " to match the start of the string",
+            'z' => " to match the end of the string",
+            _ => "",
+        };
+        return Some(format!("Replace `{str}` with `{replacement}`{purpose}"));
+    }
 
-    Some(match iter.next() {
-        Some('b') => "Replace `\\b` with `%` to match a word boundary".into(),
-        Some('B') => "Replace `\\B` with `!%` to match a place without a word boundary".into(),
-        Some('A') => "Replace `\\A` with `Start` to match the start of the string".into(),
-        Some('z') => "Replace `\\z` with `End` to match the end of the string".into(),
-        Some('Z') => "\\Z is not supported. Use `End` to match the end of the string.\n\
+    Some(match c {
+        'Z' => "\\Z is not supported. Use `End` to match the end of the string.\n\
             Note, however, that `End` doesn't match the position before the final newline."
             .into(),
-        Some('N') => "Replace `\\N` with `![n]`".into(),
-        Some('X') => "Replace `\\X` with `Grapheme`".into(),
-        Some('R') => "Replace `\\R` with `([r] [n] | [v])`".into(),
-        Some('D') => "Replace `\\D` with `[!d]`".into(),
-        Some('W') => "Replace `\\W` with `[!w]`".into(),
-        Some('S') => "Replace `\\S` with `[!s]`".into(),
-        Some('V') => "Replace `\\V` with `![v]`".into(),
-        Some('H') => "Replace `\\H` with `![h]`".into(),
-        Some('G') => "Match attempt anchors are not supported".into(),
-        Some(c @ ('a' | 'e' | 'f' | 'n' | 'r' | 't' | 'h' | 'v' | 'd' | 'w' | 's')) => {
-            format!("Replace `\\{c}` with `[{c}]`")
-        }
-        Some('0') => "Replace `\\0` with `U+00`".into(),
-        Some(c @ '1'..='7') => format!(
+        'G' => "Match attempt anchors are not supported".into(),
+        '1'..='7' => format!(
             "If this is a backreference, replace it with `::{c}`.\n\
             If this is an octal escape, replace it with `U+0{c}`."
         ),
-        Some(c @ '1'..='9') => format!("Replace `\\{c}` with `::{c}`"),
         _ => return None,
     })
 }
 
-fn get_backslash_help_u4(str: &str) -> String {
-    // \uFFFF
-    let hex = &str[2..];
-    format!("Try `U+{hex}` instead")
-}
-
-fn get_backslash_help_x2(str: &str) -> String {
-    // \xFF
-    let hex = &str[2..];
-    format!("Try `U+{hex}` instead")
-}
-
-fn get_backslash_help_unicode(str: &str) -> String {
-    // \u{...}, \x{...}
-    let hex = str[2..].trim_matches(&['{', '}'][..]);
-    format!("Try `U+{hex}` instead")
-}
-
-fn get_backslash_gk_help(str: &str) -> String {
+fn get_backslash_gk_help(str: &str, replacement: Option<String>) -> Option<String> {
     // \k<name>, \k'name', \k{name}, \k0, \k-1, \k+1,
     // \g<name>, \g'name', \g{name}, \g0, \g-1, \g+1
-    let name = str[2..].trim_matches(&['{', '}', '<', '>', '\''][..]);
-
-    if name == "0" {
-        "Recursion is currently not supported".to_string()
-    } else {
-        format!("Replace `{str}` with `::{name}`")
-    }
-}
-
-fn get_backslash_property_help(str: &str) -> String {
-    // \pL, \PL, \p{Letter}, \P{Letter}, \p{^Letter}, \P{^Letter}
-    let is_negative =
-        (str.starts_with("\\P") && !str.starts_with("\\P{^")) || str.starts_with("\\p{^");
-    let name = str[2..].trim_matches(&['{', '}', '^'][..]).replace(&['+', '-'][..], "_");
-
-    if is_negative {
-        format!("Replace `{str}` with `[!{name}]`")
-    } else {
-        format!("Replace `{str}` with `[{name}]`")
+    match replacement {
+        Some(replacement) => Some(format!("Replace `{str}` with `{replacement}`")),
+        None if str[2..].trim_matches(&['{', '}', '<', '>', '\''][..]) == "0" => {
+            Some("Recursion is currently not supported".into())
+        }
+        None => None,
     }
 }
@@ -38,6 +38,14 @@ impl LexErrorMsg {
     pub fn get_help(&self, slice: &str) -> Option<String> {
         super::diagnostics::get_parse_error_msg_help(*self, slice)
     }
+
+    /// Returns the pomsky syntax that is equivalent to the invalid token, if
+    /// there is one.
+    ///
+    /// The `slice` argument must be the same string that you tried to parse.
+    pub fn get_replacement(&self, slice: &str) -> Option<String> {
+        super::diagnostics::get_parse_error_msg_replacement(*self, slice)
+    }
 }
 
 impl std::error::Error for LexErrorMsg {}
@@ -1,9 +1,11 @@
+mod comments;
 mod diagnostics;
 mod error;
 mod micro_regex;
 mod token;
 mod tokenize;
 
+pub use comments::comments;
 pub use error::LexErrorMsg;
 pub use token::Token;
 
@@ -23,6 +23,7 @@ mod pretty_print;
 pub mod diagnose;
 pub mod exprs;
 
+pub use lexer::comments;
 pub use parse::parse;
 pub use span::Span;
 
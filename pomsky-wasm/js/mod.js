@@ -26,13 +26,31 @@ export class PomskyError extends Error {
   }
 }
 
+export class PomskyGroup {
+  /**
+   * @param {number} index
+   * @param {string?} name
+   * @param {[number, number]} range
+   */
+  constructor(index, name, range) {
+    this.index = index
+    this.name = name
+    this.range = [range[0], range[1]]
+  }
+}
+
 export class PomskyResult {
   /**
    * @param {string | null} output
    * @param {PomskyDiagnostic[]} diagnostics
+   * @param {PomskyGroup[]} groups
+   * @param {string} requiredFlags
+   * @param {string} recommendedFlags
    */
-  constructor(output, diagnostics) {
+  constructor(output, diagnostics, groups, requiredFlags, recommendedFlags) {
     this.output = output
     this.diagnostics = diagnostics
+    this.groups = groups
+    this.flags = { required: requiredFlags, recommended: recommendedFlags }
   }
 }
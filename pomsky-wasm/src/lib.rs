@@ -2,8 +2,9 @@ use std::ops::Range;
 
 use pomsky::{
     diagnose::Diagnostic,
-    options::{CompileOptions, RegexFlavor},
-    Expr,
+    options::{CompileOptions, FlavorVersion, RegexFlavor},
+    output::{GroupInfo, RegexFlags},
+    Expr, Span,
 };
 use wasm_bindgen::prelude::*;
 
@@ -30,11 +31,23 @@ extern "C" {
     #[wasm_bindgen(constructor)]
     fn new(message: String) -> PomskyError;
 
+    #[wasm_bindgen(typescript_type = "PomskyGroup")]
+    pub type PomskyGroup;
+
+    #[wasm_bindgen(constructor)]
+    fn new(index: u32, name: Option<String>, range: &[usize]) -> PomskyGroup;
+
     #[wasm_bindgen(typescript_type = "PomskyResult")]
     pub type PomskyResult;
 
     #[wasm_bindgen(constructor)]
-    fn new(output: Option<String>, warnings: Vec<PomskyDiagnostic>) -> PomskyResult;
+    fn new(
+        output: Option<String>,
+        warnings: Vec<PomskyDiagnostic>,
+        groups: Vec<PomskyGroup>,
+        required_flags: String,
+        recommended_flags: String,
+    ) -> PomskyResult;
 }
 
 #[wasm_bindgen(typescript_custom_section)]
@@ -50,9 +63,22 @@ interface PomskyDiagnostic {
 
 interface PomskyError extends Error {}
 
+interface PomskyGroup {
+    index: number;
+    name: string | null;
+    range: [number, number];
+}
+
 interface PomskyResult {
     output: string | null;
     diagnostics: PomskyDiagnostic[];
+    groups: PomskyGroup[];
+    flags: PomskyFlags;
+}
+
+interface PomskyFlags {
+    required: string;
+    recommended: string;
 }
 "#;
 
@@ -66,54 +92,82 @@ interface PomskyResult {
 ///  - "dotnet" or ".net"
 ///  - "pcre"
 ///  - "python"
+///  - "python-regex"
 ///  - "ruby"
 ///  - "rust"
+///  - "re2" or "go"
+///  - "posix-extended" or "ere"
+///  - "icu"
+///  - "vim"
+///  - "emacs"
+///
+/// The version of JavaScript or Python can be appended after `@`, e.g.
+/// "js@es2018" or "python@3.11".
 pub fn compile(input: &str, flavor: &str) -> Result<PomskyResult, PomskyError> {
     utils::set_panic_hook();
 
-    let flavor = parse_flavor(flavor)
+    let (flavor, flavor_version) = parse_flavor(flavor)
         .ok_or_else(|| PomskyError::new(format!("Unknown regex flavor `{flavor}`")))?;
 
-    let (result, diagnostics) = Expr::parse_and_compile(
+    let result = Expr::parse_and_compile_detailed(
         input,
-        CompileOptions { flavor, max_range_size: 12, ..Default::default() },
+        CompileOptions { flavor, flavor_version, max_range_size: 12, ..Default::default() },
     );
+    let (output, groups, flags, diagnostics) = match result {
+        Ok(compiled) => {
+            let output = compiled.output;
+            (Some(output.regex), output.groups, output.flags, compiled.warnings)
+        }
+        Err(failure) => {
+            let mut diagnostics = failure.errors;
+            diagnostics.extend(failure.warnings);
+            (None, vec![], RegexFlags::default(), diagnostics)
+        }
+    };
 
     Ok(PomskyResult::new(
-        result,
+        output,
         diagnostics.into_iter().map(|d| convert_diagnostic(input, d)).collect(),
+        groups.into_iter().map(|g| convert_group(input, g)).collect(),
+        flags.required,
+        flags.recommended,
     ))
 }
 
-fn parse_flavor(flavor: &str) -> Option<RegexFlavor> {
-    Some(match flavor {
-        "javascript" | "js" => RegexFlavor::JavaScript,
-        "java" => RegexFlavor::Java,
-        "dotnet" | ".net" => RegexFlavor::DotNet,
-        "pcre" => RegexFlavor::Pcre,
-        "python" => RegexFlavor::Python,
-        "ruby" => RegexFlavor::Ruby,
-        "rust" => RegexFlavor::Rust,
-        _ => return None,
-    })
+fn parse_flavor(flavor: &str) -> Option<(RegexFlavor, Option<FlavorVersion>)> {
+    match flavor.split_once('@') {
+        Some((flavor, version)) => {
+            let flavor = flavor.parse().ok()?;
+            Some((flavor, Some(FlavorVersion::parse(flavor, version)?)))
+        }
+        None => Some((flavor.parse().ok()?, None)),
+    }
 }
 
 fn convert_diagnostic(input: &str, d: Diagnostic) -> PomskyDiagnostic {
-    let Range { start, end } = d.span.range().unwrap_or_default();
-    let (prefix, content, _) = split_in_three(input, start, end);
-    let start16 = prefix.encode_utf16().count();
-    let end16 = start16 + content.encode_utf16().count();
-
     PomskyDiagnostic::new(
         d.severity.into(),
         d.kind.into(),
         d.code.map(|c| c.to_string()),
         d.msg,
         d.help,
-        &[start16, end16],
+        &convert_span(input, d.span),
     )
 }
 
+fn convert_group(input: &str, g: GroupInfo) -> PomskyGroup {
+    PomskyGroup::new(g.index, g.name, &convert_span(input, g.span))
+}
+
+/// Converts a span to a range of UTF-16 code units, as used by JavaScript
+fn convert_span(input: &str, span: Span) -> [usize; 2] {
+    let Range { start, end } = span.range().unwrap_or_default();
+    let (prefix, content, _) = split_in_three(input, start, end);
+    let start16 = prefix.encode_utf16().count();
+    let end16 = start16 + content.encode_utf16().count();
+    [start16, end16]
+}
+
 fn split_in_three(input: &str, cut1: usize, cut2: usize) -> (&str, &str, &str) {
     let (rest, suffix) = input.split_at(cut2);
     let (prefix, middle) = rest.split_at(cut1);